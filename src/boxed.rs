@@ -0,0 +1,62 @@
+use alloc::{ boxed::Box, rc::Rc };
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+
+use super::traits::Parser;
+
+/// A type-erased parser; being a `Box<dyn Fn>` it can be passed anywhere a `parser!` is expected
+pub type BoxedParser<'a, Iter, Err, T> = Box<dyn Fn(&mut Iter) -> Result<T, Err> + 'a>;
+
+/// A type-erased parser that can be cheaply cloned and shared between rules
+pub type RcParser<'a, Iter, Err, T> = Rc<dyn Fn(&mut Iter) -> Result<T, Err> + 'a>;
+
+/// The thread-safe counterpart of `RcParser`
+#[cfg(target_has_atomic = "ptr")]
+pub type ArcParser<'a, Iter, Err, T> = Arc<dyn Fn(&mut Iter) -> Result<T, Err> + Send + Sync + 'a>;
+
+/// Erases the type of a parser
+pub fn boxed<'a, Iter, Err, T>(
+    parser: impl Fn(&mut Iter) -> Result<T, Err> + 'a
+)
+    -> BoxedParser<'a, Iter, Err, T>
+{
+    Box::new(parser)
+}
+
+/// Erases the type of a parser behind an `Rc`
+pub fn shared<'a, Iter, Err, T>(
+    parser: impl Fn(&mut Iter) -> Result<T, Err> + 'a
+)
+    -> RcParser<'a, Iter, Err, T>
+{
+    Rc::new(parser)
+}
+
+/// Erases the type of a parser behind an `Arc`
+#[cfg(target_has_atomic = "ptr")]
+pub fn shared_sync<'a, Iter, Err, T>(
+    parser: impl Fn(&mut Iter) -> Result<T, Err> + Send + Sync + 'a
+)
+    -> ArcParser<'a, Iter, Err, T>
+{
+    Arc::new(parser)
+}
+
+impl<'a, Iter, Err, T> Parser<Iter> for RcParser<'a, Iter, Err, T> {
+    type Output = T;
+    type Error = Err;
+
+    fn parse(&self, iter: &mut Iter) -> Result<T, Err> {
+        self(iter)
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<'a, Iter, Err, T> Parser<Iter> for ArcParser<'a, Iter, Err, T> {
+    type Output = T;
+    type Error = Err;
+
+    fn parse(&self, iter: &mut Iter) -> Result<T, Err> {
+        self(iter)
+    }
+}
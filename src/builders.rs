@@ -1,4 +1,4 @@
-use super::parser;
+use super::{ parser, utils::next_chunk, state::{ Snapshot, look_back } };
 
 pub const fn eos<Iter: Iterator, Err>(
     msg: impl Fn(&Iter) -> Err
@@ -24,13 +24,13 @@ where
 {
     move |iter| {
         let err = msg(iter);
-        match iter.next_chunk::<N>() {
-            Result::Ok(b) => if a == b {
+        match next_chunk::<Iter, N>(iter) {
+            Option::Some(b) => if a == b {
                 Result::Ok(b)
             } else {
                 Result::Err(err)
             },
-            Result::Err(_into) => Result::Err(err)
+            Option::None => Result::Err(err)
         }
     }
 }
@@ -43,13 +43,70 @@ pub const fn predicate<Iter: Iterator, Err, const N: usize>(
 {
     move |iter| {
         let err = msg(iter);
-        match iter.next_chunk::<N>() {
-            Result::Ok(a) => if f(& a) {
+        match next_chunk::<Iter, N>(iter) {
+            Option::Some(a) => if f(& a) {
                 Result::Ok(a)
             } else {
                 Result::Err(err)
             },
-            Result::Err(_into) => Result::Err(err)
+            Option::None => Result::Err(err)
+        }
+    }
+}
+
+/// Like `eos` but only snapshots the position up front, calling `msg` on error path
+pub const fn eos_lazy<Iter: Iterator + Snapshot, Err>(
+    msg: impl Fn(&Iter) -> Err
+)
+    -> parser![Iter, Err, ()]
+{
+    move |iter| {
+        let pre: Iter::Token = iter.save();
+        match iter.next() {
+            Option::Some(_token) => Result::Err(look_back(iter, pre, & msg)),
+            Option::None => Result::Ok(())
+        }
+    }
+}
+
+/// Like `expect` but only snapshots the position up front, calling `msg` on error path
+pub const fn expect_lazy<Iter: Iterator + Snapshot, Err, const N: usize>(
+    a: [Iter::Item; N],
+    msg: impl Fn(&Iter) -> Err
+)
+    -> parser![Iter, Err, [Iter::Item; N]]
+where
+    Iter::Item: PartialEq
+{
+    move |iter| {
+        let pre: Iter::Token = iter.save();
+        match next_chunk::<Iter, N>(iter) {
+            Option::Some(b) => if a == b {
+                Result::Ok(b)
+            } else {
+                Result::Err(look_back(iter, pre, & msg))
+            },
+            Option::None => Result::Err(look_back(iter, pre, & msg))
+        }
+    }
+}
+
+/// Like `predicate` but only snapshots the position up front, calling `msg` on error path
+pub const fn predicate_lazy<Iter: Iterator + Snapshot, Err, const N: usize>(
+    f: impl Fn(& [Iter::Item; N]) -> bool,
+    msg: impl Fn(&Iter) -> Err
+)
+    -> parser![Iter, Err, [Iter::Item; N]]
+{
+    move |iter| {
+        let pre: Iter::Token = iter.save();
+        match next_chunk::<Iter, N>(iter) {
+            Option::Some(a) => if f(& a) {
+                Result::Ok(a)
+            } else {
+                Result::Err(look_back(iter, pre, & msg))
+            },
+            Option::None => Result::Err(look_back(iter, pre, & msg))
         }
     }
 }
\ No newline at end of file
@@ -1,59 +1,66 @@
-use super::{ parser, errors::*, monadic::* };
+use core::ops::{ Bound, RangeBounds };
+use alloc::{ vec, vec::Vec, string::String };
 
+use super::{ parser, errors::*, boxed::BoxedParser, state::{ Checkpoint, Position } };
+
+/// Parses as many as possible and then the end, giving back up a level whenever the end fails to follow
+/// (behaves as `otherwise(fmap2(cons, parser, most_till(parser, end)), end)` without recursing)
 pub const fn most_till<Iter, Err, T, U>(
     parser: parser![Iter, Err, T],
     end: parser![Iter, Err, U]
 )
     -> parser![Iter, Vec<Err>, (Vec<T>, U)]
 {
-    fn most_till_inner<'a, Iter_, Err_, T_, U_>(
-        parser_: &'a parser![Iter_, Err_, T_],
-        end_: &'a parser![Iter_, Err_, U_],
-        iter: &mut Iter_
-    )
-        -> Result<(Vec<T_>, U_), Vec<Err_>>
-    {
-        otherwise(
-            fmap2(
-                |t, (mut ts, u): (Vec<T_>, U_)| { ts.insert(0, t); (ts, u) },
-                wrap_err(parser_),
-                |iter| most_till_inner::<Iter_, Err_, T_, U_>(parser_, end_, iter)
-            ),
-            fmap(
-                |u| (vec![], u),
-                wrap_err(end_)
-            )
-        )(iter)
-    }
-    move |iter| most_till_inner(& parser, & end, iter)
+    move |iter| {
+        let mut ts: Vec<T> = vec![];
+        let mut errs: Vec<Err> = loop {
+            match parser(iter) {
+                Result::Ok(t) => ts.push(t),
+                Result::Err(err) => break vec![err]
+            }
+        };
+        let mut level: usize = ts.len();
+        loop {
+            match end(iter) {
+                Result::Ok(u) => {
+                    ts.truncate(level);
+                    return Result::Ok((ts, u));
+                },
+                Result::Err(err) => errs.push(err)
+            };
+            if level == 0 {
+                return Result::Err(errs);
+            }
+            level -= 1;
+        }
+    }
 }
 
+/// Parses the end as soon as possible, parsing another item whenever it fails
+/// (behaves as `otherwise(end, fmap2(cons, parser, least_till(parser, end)))` without recursing)
 pub const fn least_till<Iter, Err, T, U>(
     parser: parser![Iter, Err, T],
     end: parser![Iter, Err, U]
 )
     -> parser![Iter, Vec<Err>, (Vec<T>, U)]
 {
-    fn least_till_inner<'a, Iter_, Err_, T_, U_>(
-        parser_: &'a parser![Iter_, Err_, T_],
-        end_: &'a parser![Iter_, Err_, U_],
-        iter: &mut Iter_
-    )
-        -> Result<(Vec<T_>, U_), Vec<Err_>>
-    {
-        otherwise(
-            fmap(
-                |u| (vec![], u),
-                wrap_err(end_)
-            ),
-            fmap2(
-                |t, (mut ts, u): (Vec<T_>, U_)| { ts.insert(0, t); (ts, u) },
-                wrap_err(parser_),
-                |iter| least_till_inner::<Iter_, Err_, T_, U_>(parser_, end_, iter)
-            )
-        )(iter)
-    }
-    move |iter| least_till_inner(& parser, & end, iter)
+    move |iter| {
+        let mut ts: Vec<T> = vec![];
+        let mut errs: Vec<Err> = vec![];
+        loop {
+            match end(iter) {
+                Result::Ok(u) => return Result::Ok((ts, u)),
+                Result::Err(err) => errs.push(err)
+            };
+            match parser(iter) {
+                Result::Ok(t) => ts.push(t),
+                Result::Err(err) => {
+                    errs.push(err);
+                    return Result::Err(errs);
+                }
+            };
+        }
+    }
 }
 
 pub const fn most<Iter, Err, T>(
@@ -66,19 +73,811 @@ pub const fn most<Iter, Err, T>(
         while let Result::Ok(t) = parser(iter) {
             ts.push(t);
         }
-        return Result::Ok(ts);
+        Result::Ok(ts)
     }
 }
 
-pub const fn unwind<Iter: Clone, Err, T>(
+fn never_committed<Err>(
+    _err: & Err
+)
+    -> bool
+{
+    false
+}
+
+fn repeat_with<Iter, Err, T>(
+    range: impl RangeBounds<usize>,
+    parser: parser![Iter, Err, T],
+    committed: impl Fn(& Err) -> bool
+)
+    -> parser![Iter, Vec<Err>, Vec<T>]
+{
+    let min: usize = match range.start_bound() {
+        Bound::Included(n) => *n,
+        Bound::Excluded(n) => n + 1,
+        Bound::Unbounded => 0
+    };
+    let max: Option<usize> = match range.end_bound() {
+        Bound::Included(n) => Option::Some(*n),
+        Bound::Excluded(n) => Option::Some(n.checked_sub(1).expect("repeat needs a non-empty range")),
+        Bound::Unbounded => Option::None
+    };
+    assert!(max.is_none_or(|max| min <= max), "repeat needs a non-empty range");
+    move |iter| {
+        let mut ts: Vec<T> = vec![];
+        while max.is_none_or(|max| ts.len() < max) {
+            match parser(iter) {
+                Result::Ok(t) => ts.push(t),
+                Result::Err(err) => if ts.len() < min || committed(& err) {
+                    return Result::Err(vec![err]);
+                } else {
+                    break;
+                }
+            };
+        }
+        Result::Ok(ts)
+    }
+}
+
+/// Parses between `min` and `max` items of a range, stopping early at the first failure once `min` is reached
+/// (panics on an empty range)
+pub fn repeat<Iter, Err, T>(
+    range: impl RangeBounds<usize>,
+    parser: parser![Iter, Err, T]
+)
+    -> parser![Iter, Vec<Err>, Vec<T>]
+{
+    repeat_with(range, parser, never_committed)
+}
+
+/// As `repeat`, but a failure that passed an `errors::cut` is returned rather than stopping
+pub fn repeat_cut<Iter, Err: Commit, T>(
+    range: impl RangeBounds<usize>,
+    parser: parser![Iter, Err, T]
+)
+    -> parser![Iter, Vec<Err>, Vec<T>]
+{
+    repeat_with(range, parser, Commit::is_committed)
+}
+
+/// Parses one or more items
+pub fn many1<Iter, Err, T>(
+    parser: parser![Iter, Err, T]
+)
+    -> parser![Iter, Vec<Err>, Vec<T>]
+{
+    repeat(1.., parser)
+}
+
+/// Parses exactly `n` items
+pub fn count<Iter, Err, T>(
+    n: usize,
+    parser: parser![Iter, Err, T]
+)
+    -> parser![Iter, Vec<Err>, Vec<T>]
+{
+    repeat(n..=n, parser)
+}
+
+const fn sep_by1_with<Iter, Err, T, S>(
+    parser: parser![Iter, Err, T],
+    sep: parser![Iter, Err, S],
+    committed: impl Fn(& Err) -> bool
+)
+    -> parser![Iter, Vec<Err>, Vec<T>]
+{
+    move |iter| {
+        let mut ts: Vec<T> = match parser(iter) {
+            Result::Ok(t) => vec![t],
+            Result::Err(err) => return Result::Err(vec![err])
+        };
+        loop {
+            match sep(iter) {
+                Result::Ok(_s) => {},
+                Result::Err(err) => if committed(& err) {
+                    return Result::Err(vec![err]);
+                } else {
+                    break;
+                }
+            };
+            match parser(iter) {
+                Result::Ok(t) => ts.push(t),
+                Result::Err(err) => return Result::Err(vec![err])
+            };
+        }
+        Result::Ok(ts)
+    }
+}
+
+/// Parses one or more items separated by `sep` (an item must follow every separator)
+pub const fn sep_by1<Iter, Err, T, S>(
+    parser: parser![Iter, Err, T],
+    sep: parser![Iter, Err, S]
+)
+    -> parser![Iter, Vec<Err>, Vec<T>]
+{
+    sep_by1_with(parser, sep, never_committed)
+}
+
+/// As `sep_by1`, but a failure that passed an `errors::cut` is returned rather than stopping
+pub const fn sep_by1_cut<Iter, Err: Commit, T, S>(
+    parser: parser![Iter, Err, T],
+    sep: parser![Iter, Err, S]
+)
+    -> parser![Iter, Vec<Err>, Vec<T>]
+{
+    sep_by1_with(parser, sep, Commit::is_committed)
+}
+
+const fn sep_by_with<Iter, Err, T, S>(
+    parser: parser![Iter, Err, T],
+    sep: parser![Iter, Err, S],
+    committed: impl Fn(& Err) -> bool
+)
+    -> parser![Iter, Vec<Err>, Vec<T>]
+{
+    move |iter| {
+        let mut ts: Vec<T> = match parser(iter) {
+            Result::Ok(t) => vec![t],
+            Result::Err(err) => return if committed(& err) { Result::Err(vec![err]) } else { Result::Ok(vec![]) }
+        };
+        loop {
+            match sep(iter) {
+                Result::Ok(_s) => {},
+                Result::Err(err) => if committed(& err) {
+                    return Result::Err(vec![err]);
+                } else {
+                    break;
+                }
+            };
+            match parser(iter) {
+                Result::Ok(t) => ts.push(t),
+                Result::Err(err) => return Result::Err(vec![err])
+            };
+        }
+        Result::Ok(ts)
+    }
+}
+
+/// Parses zero or more items separated by `sep` (an item must follow every separator)
+pub const fn sep_by<Iter, Err, T, S>(
+    parser: parser![Iter, Err, T],
+    sep: parser![Iter, Err, S]
+)
+    -> parser![Iter, Vec<Err>, Vec<T>]
+{
+    sep_by_with(parser, sep, never_committed)
+}
+
+/// As `sep_by`, but a failure that passed an `errors::cut` is returned rather than stopping
+pub const fn sep_by_cut<Iter, Err: Commit, T, S>(
+    parser: parser![Iter, Err, T],
+    sep: parser![Iter, Err, S]
+)
+    -> parser![Iter, Vec<Err>, Vec<T>]
+{
+    sep_by_with(parser, sep, Commit::is_committed)
+}
+
+const fn sep_end_by1_with<Iter, Err, T, S>(
+    parser: parser![Iter, Err, T],
+    sep: parser![Iter, Err, S],
+    committed: impl Fn(& Err) -> bool
+)
+    -> parser![Iter, Vec<Err>, Vec<T>]
+{
+    move |iter| {
+        let mut ts: Vec<T> = match parser(iter) {
+            Result::Ok(t) => vec![t],
+            Result::Err(err) => return Result::Err(vec![err])
+        };
+        loop {
+            match sep(iter) {
+                Result::Ok(_s) => {},
+                Result::Err(err) => if committed(& err) {
+                    return Result::Err(vec![err]);
+                } else {
+                    break;
+                }
+            };
+            match parser(iter) {
+                Result::Ok(t) => ts.push(t),
+                Result::Err(err) => if committed(& err) {
+                    return Result::Err(vec![err]);
+                } else {
+                    break;
+                }
+            };
+        }
+        Result::Ok(ts)
+    }
+}
+
+/// Parses one or more items separated, and optionally ended, by `sep`
+pub const fn sep_end_by1<Iter, Err, T, S>(
+    parser: parser![Iter, Err, T],
+    sep: parser![Iter, Err, S]
+)
+    -> parser![Iter, Vec<Err>, Vec<T>]
+{
+    sep_end_by1_with(parser, sep, never_committed)
+}
+
+/// As `sep_end_by1`, but a failure that passed an `errors::cut` is returned rather than stopping
+pub const fn sep_end_by1_cut<Iter, Err: Commit, T, S>(
+    parser: parser![Iter, Err, T],
+    sep: parser![Iter, Err, S]
+)
+    -> parser![Iter, Vec<Err>, Vec<T>]
+{
+    sep_end_by1_with(parser, sep, Commit::is_committed)
+}
+
+const fn sep_end_by_with<Iter, Err, T, S>(
+    parser: parser![Iter, Err, T],
+    sep: parser![Iter, Err, S],
+    committed: impl Fn(& Err) -> bool
+)
+    -> parser![Iter, Vec<Err>, Vec<T>]
+{
+    move |iter| {
+        let mut ts: Vec<T> = vec![];
+        loop {
+            match parser(iter) {
+                Result::Ok(t) => ts.push(t),
+                Result::Err(err) => if committed(& err) {
+                    return Result::Err(vec![err]);
+                } else {
+                    break;
+                }
+            };
+            match sep(iter) {
+                Result::Ok(_s) => {},
+                Result::Err(err) => if committed(& err) {
+                    return Result::Err(vec![err]);
+                } else {
+                    break;
+                }
+            };
+        }
+        Result::Ok(ts)
+    }
+}
+
+/// Parses zero or more items separated, and optionally ended, by `sep`
+pub const fn sep_end_by<Iter, Err, T, S>(
+    parser: parser![Iter, Err, T],
+    sep: parser![Iter, Err, S]
+)
+    -> parser![Iter, Vec<Err>, Vec<T>]
+{
+    sep_end_by_with(parser, sep, never_committed)
+}
+
+/// As `sep_end_by`, but a failure that passed an `errors::cut` is returned rather than stopping
+pub const fn sep_end_by_cut<Iter, Err: Commit, T, S>(
+    parser: parser![Iter, Err, T],
+    sep: parser![Iter, Err, S]
+)
+    -> parser![Iter, Vec<Err>, Vec<T>]
+{
+    sep_end_by_with(parser, sep, Commit::is_committed)
+}
+
+/// Parses `open`, the item and then `close`, keeping only the item
+pub const fn between<Iter, Err, O, T, C>(
+    open: parser![Iter, Err, O],
+    parser: parser![Iter, Err, T],
+    close: parser![Iter, Err, C]
+)
+    -> parser![Iter, Vec<Err>, T]
+{
+    move |iter| {
+        if let Result::Err(err) = open(iter) {
+            return Result::Err(vec![err]);
+        }
+        let t: T = match parser(iter) {
+            Result::Ok(t) => t,
+            Result::Err(err) => return Result::Err(vec![err])
+        };
+        match close(iter) {
+            Result::Ok(_c) => Result::Ok(t),
+            Result::Err(err) => Result::Err(vec![err])
+        }
+    }
+}
+
+const fn fold_many_with<Iter, Err, T, A>(
+    parser: parser![Iter, Err, T],
+    init: impl Fn() -> A,
+    f: impl Fn(A, T) -> A,
+    committed: impl Fn(& Err) -> bool
+)
+    -> parser![Iter, Err, A]
+{
+    move |iter| {
+        let mut acc: A = init();
+        loop {
+            match parser(iter) {
+                Result::Ok(t) => acc = f(acc, t),
+                Result::Err(err) => if committed(& err) {
+                    return Result::Err(err);
+                } else {
+                    break;
+                }
+            };
+        }
+        Result::Ok(acc)
+    }
+}
+
+/// Like `most` but folds each item into an accumulator instead of collecting them
+pub const fn fold_many<Iter, Err, T, A>(
+    parser: parser![Iter, Err, T],
+    init: impl Fn() -> A,
+    f: impl Fn(A, T) -> A
+)
+    -> parser![Iter, Err, A]
+{
+    fold_many_with(parser, init, f, never_committed)
+}
+
+/// As `fold_many`, but a failure that passed an `errors::cut` is returned rather than stopping
+pub const fn fold_many_cut<Iter, Err: Commit, T, A>(
+    parser: parser![Iter, Err, T],
+    init: impl Fn() -> A,
+    f: impl Fn(A, T) -> A
+)
+    -> parser![Iter, Err, A]
+{
+    fold_many_with(parser, init, f, Commit::is_committed)
+}
+
+const fn chainl1_with<Iter, Err, T, F: FnOnce(T, T) -> T>(
+    term: parser![Iter, Err, T],
+    op: parser![Iter, Err, F],
+    committed: impl Fn(& Err) -> bool
+)
+    -> parser![Iter, Vec<Err>, T]
+{
+    move |iter| {
+        let mut acc: T = match term(iter) {
+            Result::Ok(t) => t,
+            Result::Err(err) => return Result::Err(vec![err])
+        };
+        loop {
+            let f: F = match op(iter) {
+                Result::Ok(f) => f,
+                Result::Err(err) => if committed(& err) {
+                    return Result::Err(vec![err]);
+                } else {
+                    break;
+                }
+            };
+            match term(iter) {
+                Result::Ok(t) => acc = f(acc, t),
+                Result::Err(err) => return Result::Err(vec![err])
+            };
+        }
+        Result::Ok(acc)
+    }
+}
+
+/// Parses one or more terms separated by operators, applying them left-associatively as it goes
+/// (a term must follow every operator)
+pub const fn chainl1<Iter, Err, T, F: FnOnce(T, T) -> T>(
+    term: parser![Iter, Err, T],
+    op: parser![Iter, Err, F]
+)
+    -> parser![Iter, Vec<Err>, T]
+{
+    chainl1_with(term, op, never_committed)
+}
+
+/// As `chainl1`, but an operator failure that passed an `errors::cut` is returned rather than ending the chain
+pub const fn chainl1_cut<Iter, Err: Commit, T, F: FnOnce(T, T) -> T>(
+    term: parser![Iter, Err, T],
+    op: parser![Iter, Err, F]
+)
+    -> parser![Iter, Vec<Err>, T]
+{
+    chainl1_with(term, op, Commit::is_committed)
+}
+
+const fn chainr1_with<Iter, Err, T, F: FnOnce(T, T) -> T>(
+    term: parser![Iter, Err, T],
+    op: parser![Iter, Err, F],
+    committed: impl Fn(& Err) -> bool
+)
+    -> parser![Iter, Vec<Err>, T]
+{
+    move |iter| {
+        let mut acc: T = match term(iter) {
+            Result::Ok(t) => t,
+            Result::Err(err) => return Result::Err(vec![err])
+        };
+        let mut pending: Vec<(T, F)> = vec![];
+        loop {
+            let f: F = match op(iter) {
+                Result::Ok(f) => f,
+                Result::Err(err) => if committed(& err) {
+                    return Result::Err(vec![err]);
+                } else {
+                    break;
+                }
+            };
+            match term(iter) {
+                Result::Ok(t) => pending.push((core::mem::replace(&mut acc, t), f)),
+                Result::Err(err) => return Result::Err(vec![err])
+            };
+        }
+        while let Option::Some((t, f)) = pending.pop() {
+            acc = f(t, acc);
+        }
+        Result::Ok(acc)
+    }
+}
+
+/// Parses one or more terms separated by operators, applying them right-associatively once the chain ends
+/// (a term must follow every operator; only the pending left operands are kept)
+pub const fn chainr1<Iter, Err, T, F: FnOnce(T, T) -> T>(
+    term: parser![Iter, Err, T],
+    op: parser![Iter, Err, F]
+)
+    -> parser![Iter, Vec<Err>, T]
+{
+    chainr1_with(term, op, never_committed)
+}
+
+/// As `chainr1`, but an operator failure that passed an `errors::cut` is returned rather than ending the chain
+pub const fn chainr1_cut<Iter, Err: Commit, T, F: FnOnce(T, T) -> T>(
+    term: parser![Iter, Err, T],
+    op: parser![Iter, Err, F]
+)
+    -> parser![Iter, Vec<Err>, T]
+{
+    chainr1_with(term, op, Commit::is_committed)
+}
+
+/// Like `most` but also returns the error that stopped it
+pub const fn most_with_err<Iter, Err, T>(
+    parser: parser![Iter, Err, T]
+)
+    -> parser![Iter, Err, (Vec<T>, Err)]
+{
+    move |iter| {
+        let mut ts: Vec<T> = vec![];
+        loop {
+            match parser(iter) {
+                Result::Ok(t) => ts.push(t),
+                Result::Err(err) => return Result::Ok((ts, err))
+            };
+        }
+    }
+}
+
+/// Like `most_with_err` but fails using `msg` if the parser succeeds without consuming input (which would loop forever)
+pub const fn most_checked<Iter: Position, Err, T>(
+    parser: parser![Iter, Err, T],
+    msg: impl Fn(&Iter) -> Err
+)
+    -> parser![Iter, Err, (Vec<T>, Err)]
+{
+    move |iter| {
+        let mut ts: Vec<T> = vec![];
+        loop {
+            let pre: Iter::Pos = iter.position();
+            match parser(iter) {
+                Result::Ok(_t) if iter.position() == pre => return Result::Err(msg(iter)),
+                Result::Ok(t) => ts.push(t),
+                Result::Err(err) => return Result::Ok((ts, err))
+            };
+        }
+    }
+}
+
+pub const fn unwind<Iter: Checkpoint, Err, T>(
     parser: parser![Iter, Err, T]
 )
     -> parser![Iter, Err, T]
 {
     move |iter| {
-        let pre: Iter = iter.clone();
+        let pre: Iter::Token = iter.save();
         let res = parser(iter);
-        *iter = pre;
+        iter.restore(pre);
         res
     }
-}
\ No newline at end of file
+}
+
+/// Parses an item if possible
+/// (DOES 👏 NOT 👏 REWIND 👏, use `errors::try_parse` for that)
+pub const fn optional<Iter, Err, T>(
+    parser: parser![Iter, Err, T]
+)
+    -> parser![Iter, Err, Option<T>]
+{
+    move |iter| Result::Ok(parser(iter).ok())
+}
+
+/// As `optional`, but returns failures that passed an `errors::cut`
+pub const fn optional_cut<Iter, Err: Commit, T>(
+    parser: parser![Iter, Err, T]
+)
+    -> parser![Iter, Err, Option<T>]
+{
+    move |iter| match parser(iter) {
+        Result::Ok(t) => Result::Ok(Option::Some(t)),
+        Result::Err(err) if err.is_committed() => Result::Err(err),
+        Result::Err(_err) => Result::Ok(Option::None)
+    }
+}
+
+fn choice_with<'a, Iter, Err, T>(
+    parsers: impl AsRef<[BoxedParser<'a, Iter, Err, T>]>,
+    committed: impl Fn(& Err) -> bool
+)
+    -> parser![Iter, Vec<Err>, T]
+{
+    move |iter| {
+        let mut errs: Vec<Err> = vec![];
+        for parser in parsers.as_ref() {
+            match parser(iter) {
+                Result::Ok(t) => return Result::Ok(t),
+                Result::Err(err) => {
+                    let stop: bool = committed(& err);
+                    errs.push(err);
+                    if stop {
+                        break;
+                    }
+                }
+            };
+        }
+        Result::Err(errs)
+    }
+}
+
+/// (<|>) Runs each parser in turn until one succeeds, like `alternative!` over a list built at runtime
+/// (DOES 👏 NOT 👏 REWIND 👏, use `errors::try_parse` for that)
+pub fn choice<'a, Iter, Err, T>(
+    parsers: impl AsRef<[BoxedParser<'a, Iter, Err, T>]>
+)
+    -> parser![Iter, Vec<Err>, T]
+{
+    choice_with(parsers, never_committed)
+}
+
+/// As `choice`, but like `alternative_cut!` stops at a failure that passed an `errors::cut`
+pub fn choice_cut<'a, Iter, Err: Commit, T>(
+    parsers: impl AsRef<[BoxedParser<'a, Iter, Err, T>]>
+)
+    -> parser![Iter, Vec<Err>, T]
+{
+    choice_with(parsers, Commit::is_committed)
+}
+
+fn permutation_with<'a, Iter: 'a, Err: 'a, T: 'a>(
+    parsers: Vec<(bool, BoxedParser<'a, Iter, Err, T>)>,
+    committed: impl Fn(& Err) -> bool + 'a
+)
+    -> impl Fn(&mut Iter) -> Result<Vec<Option<T>>, Vec<Err>> + 'a
+{
+    move |iter| {
+        let mut ts: Vec<Option<T>> = parsers.iter().map(|_parser| Option::None).collect();
+        'rounds: loop {
+            let mut errs: Vec<Err> = vec![];
+            for (i, (_optional, parser)) in parsers.iter().enumerate() {
+                if ts[i].is_some() {
+                    continue;
+                }
+                match parser(iter) {
+                    Result::Ok(t) => {
+                        ts[i] = Option::Some(t);
+                        continue 'rounds;
+                    },
+                    Result::Err(err) => {
+                        let stop: bool = committed(& err);
+                        errs.push(err);
+                        if stop {
+                            return Result::Err(errs);
+                        }
+                    }
+                };
+            }
+            let done: bool = parsers
+                .iter()
+                .zip(ts.iter())
+                .all(|((optional, _parser), t)| *optional || t.is_some());
+            return if done { Result::Ok(ts) } else { Result::Err(errs) };
+        }
+    }
+}
+
+/// Parses every parser once in any order, giving back results in the order of `parsers`
+/// (each is paired with whether it is optional; DOES 👏 NOT 👏 REWIND 👏, use `errors::try_parse` for that)
+pub fn permutation<'a, Iter: 'a, Err: 'a, T: 'a>(
+    parsers: Vec<(bool, BoxedParser<'a, Iter, Err, T>)>
+)
+    -> impl Fn(&mut Iter) -> Result<Vec<Option<T>>, Vec<Err>> + 'a
+{
+    permutation_with(parsers, never_committed)
+}
+
+/// As `permutation`, but stops at a failure that passed an `errors::cut`
+pub fn permutation_cut<'a, Iter: 'a, Err: Commit + 'a, T: 'a>(
+    parsers: Vec<(bool, BoxedParser<'a, Iter, Err, T>)>
+)
+    -> impl Fn(&mut Iter) -> Result<Vec<Option<T>>, Vec<Err>> + 'a
+{
+    permutation_with(parsers, Commit::is_committed)
+}
+
+/// (lookahead) Runs the parser without consuming input, the same as `unwind`
+pub const fn peek<Iter: Checkpoint, Err, T>(
+    parser: parser![Iter, Err, T]
+)
+    -> parser![Iter, Err, T]
+{
+    unwind(parser)
+}
+
+/// (positive lookahead) Succeeds if the parser would succeed, without consuming input
+pub const fn followed_by<Iter: Checkpoint, Err, T>(
+    parser: parser![Iter, Err, T]
+)
+    -> parser![Iter, Err, ()]
+{
+    move |iter| {
+        let pre: Iter::Token = iter.save();
+        let res = parser(iter);
+        iter.restore(pre);
+        res.map(|_t| ())
+    }
+}
+
+/// (negative lookahead) Succeeds if the parser would fail, without consuming input;
+/// otherwise `msg` is given the state and what the parser unexpectedly found
+pub const fn not_followed_by<Iter: Checkpoint, Err, T>(
+    parser: parser![Iter, Err, T],
+    msg: impl Fn(&Iter, T) -> Err
+)
+    -> parser![Iter, Err, ()]
+{
+    move |iter| {
+        let pre: Iter::Token = iter.save();
+        let res = parser(iter);
+        iter.restore(pre);
+        match res {
+            Result::Ok(t) => Result::Err(msg(iter, t)),
+            Result::Err(_err) => Result::Ok(())
+        }
+    }
+}
+
+/// Associativity of an infix operator in `expression`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Assoc {
+    Left,
+    Right,
+    None
+}
+
+/// An entry of the operator table given to `expression`; higher binding powers bind tighter
+pub enum Operator<'a, Iter, Err, O> {
+    Prefix(usize, BoxedParser<'a, Iter, Err, O>),
+    Infix(Assoc, usize, BoxedParser<'a, Iter, Err, O>),
+    Postfix(usize, BoxedParser<'a, Iter, Err, O>),
+    /// The opening and closing operators, as in `c ? t : f`
+    Ternary(usize, BoxedParser<'a, Iter, Err, O>, BoxedParser<'a, Iter, Err, O>)
+}
+
+/// The shape of an operator application, given to the AST builder of `expression`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Fold<O, T> {
+    Prefix(O, T),
+    Infix(T, O, T),
+    Postfix(T, O),
+    Ternary(T, O, T, O, T)
+}
+
+fn expression_inner<'a, Iter: Checkpoint, Info, O, T>(
+    atom: & parser![Iter, ParseError<Info>, T],
+    operators: & [Operator<'a, Iter, ParseError<Info>, O>],
+    build: & impl Fn(Fold<O, T>) -> T,
+    info_getter: & impl Fn(&Iter) -> Info,
+    min_bp: usize,
+    iter: &mut Iter
+)
+    -> Result<T, ParseError<Info>>
+{
+    let mut lhs: T = 'prefix: {
+        for operator in operators {
+            if let Operator::Prefix(bp, op) = operator {
+                let pre: Iter::Token = iter.save();
+                match op(iter) {
+                    Result::Ok(o) => {
+                        iter.release(pre);
+                        let rhs: T = expression_inner(atom, operators, build, info_getter, 2 * bp, iter)?;
+                        break 'prefix build(Fold::Prefix(o, rhs));
+                    },
+                    Result::Err(_err) => iter.restore(pre)
+                }
+            }
+        }
+        atom(iter)?
+    };
+    let mut chained: Option<usize> = Option::None;
+    'ops: loop {
+        for operator in operators {
+            match operator {
+                Operator::Prefix(_bp, _op) => {},
+                Operator::Postfix(bp, op) => if 2 * bp >= min_bp {
+                    let pre: Iter::Token = iter.save();
+                    match op(iter) {
+                        Result::Ok(o) => {
+                            iter.release(pre);
+                            lhs = build(Fold::Postfix(lhs, o));
+                            chained = Option::None;
+                            continue 'ops;
+                        },
+                        Result::Err(_err) => iter.restore(pre)
+                    }
+                },
+                Operator::Infix(assoc, bp, op) => {
+                    let (l_bp, r_bp) = match assoc {
+                        Assoc::Right => (2 * bp + 1, 2 * bp),
+                        _ => (2 * bp, 2 * bp + 1)
+                    };
+                    if l_bp >= min_bp {
+                        let pre: Iter::Token = iter.save();
+                        match op(iter) {
+                            Result::Ok(o) => {
+                                if *assoc == Assoc::None && chained == Option::Some(*bp) {
+                                    iter.restore(pre);
+                                    return Result::Err(ParseError::Message(
+                                        String::from("parentheses around chained non-associative operators"),
+                                        info_getter(iter)
+                                    ));
+                                }
+                                iter.release(pre);
+                                let rhs: T = expression_inner(atom, operators, build, info_getter, r_bp, iter)?;
+                                lhs = build(Fold::Infix(lhs, o, rhs));
+                                chained = if *assoc == Assoc::None { Option::Some(*bp) } else { Option::None };
+                                continue 'ops;
+                            },
+                            Result::Err(_err) => iter.restore(pre)
+                        }
+                    }
+                },
+                Operator::Ternary(bp, open, close) => if 2 * bp + 1 >= min_bp {
+                    let pre: Iter::Token = iter.save();
+                    match open(iter) {
+                        Result::Ok(o0) => {
+                            iter.release(pre);
+                            let mid: T = expression_inner(atom, operators, build, info_getter, 0, iter)?;
+                            let o1: O = close(iter)?;
+                            let rhs: T = expression_inner(atom, operators, build, info_getter, 2 * bp, iter)?;
+                            lhs = build(Fold::Ternary(lhs, o0, mid, o1, rhs));
+                            chained = Option::None;
+                            continue 'ops;
+                        },
+                        Result::Err(_err) => iter.restore(pre)
+                    }
+                }
+            }
+        }
+        return Result::Ok(lhs);
+    }
+}
+
+/// (pratt) Parses operator expressions over `atom`, folding each operator application into a `T` with `build`
+/// (operators that fail are rewound, so they need not be wrapped in `try_parse`)
+pub fn expression<'a, Iter: Checkpoint + 'a, Info: 'a, O: 'a, T: 'a>(
+    atom: impl Fn(&mut Iter) -> Result<T, ParseError<Info>> + 'a,
+    operators: Vec<Operator<'a, Iter, ParseError<Info>, O>>,
+    build: impl Fn(Fold<O, T>) -> T + 'a,
+    info_getter: impl Fn(&Iter) -> Info + 'a
+)
+    -> impl Fn(&mut Iter) -> Result<T, ParseError<Info>> + 'a
+{
+    move |iter| expression_inner(& atom, & operators, & build, & info_getter, 0, iter)
+}
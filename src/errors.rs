@@ -1,4 +1,7 @@
-use super::parser;
+use core::fmt::{ self, Debug, Display, Formatter };
+use alloc::{ vec, vec::Vec, string::String, boxed::Box, format };
+
+use super::{ parser, state::{ Checkpoint, Snapshot, ErrorLog, look_back } };
 
 /// Starts the error path using the state
 pub const fn fail<Iter, Err, T>(
@@ -32,17 +35,30 @@ pub const fn fmap_err_with_state<Iter, Err, Frr, T, G: FnOnce(Err) -> Frr>(
     }
 }
 
-/// Copies the state before parsing and sets the state back on error path
-pub const fn try_parse<Iter: Clone, Err, T>(
+/// Like `fmap_err_with_state` but only snapshots the position up front, looking back at the state on error path
+pub const fn fmap_err_with_state_lazy<Iter: Snapshot, Err, Frr, T>(
+    f: impl Fn(&Iter, Err) -> Frr,
+    parser: parser![Iter, Err, T]
+)
+    -> parser![Iter, Frr, T]
+{
+    move |iter| {
+        let pre: Iter::Token = iter.save();
+        parser(iter).map_err(|err| look_back(iter, pre, |iter_| f(iter_, err)))
+    }
+}
+
+/// Saves a checkpoint of the state before parsing and rewinds to it on error path
+pub const fn try_parse<Iter: Checkpoint, Err, T>(
     parser: parser![Iter, Err, T]
 )
     -> parser![Iter, Err, T]
 {
     move |iter| {
-        let pre: Iter = iter.clone();
+        let pre: Iter::Token = iter.save();
         match parser(iter) {
-            Result::Ok(t) => Result::Ok(t),
-            Result::Err(err) => {*iter = pre; Result::Err(err)}
+            Result::Ok(t) => {iter.release(pre); Result::Ok(t)},
+            Result::Err(err) => {iter.restore(pre); Result::Err(err)}
         }
     }
 }
@@ -74,6 +90,46 @@ pub const fn recover_with<Iter, Err, Frr, T>(
     }
 }
 
+/// Discards input until the synchronisation parser succeeds (consuming what it parses, wrap it in `combinators::peek` to keep it),
+/// failing with its error if the input runs out first
+pub const fn skip_until<Iter: Iterator + Checkpoint, Err, S>(
+    sync: parser![Iter, Err, S]
+)
+    -> parser![Iter, Err, S]
+{
+    move |iter| loop {
+        let pre: Iter::Token = iter.save();
+        match sync(iter) {
+            Result::Ok(s) => {
+                iter.release(pre);
+                return Result::Ok(s);
+            },
+            Result::Err(err) => {
+                iter.restore(pre);
+                if iter.next().is_none() {
+                    return Result::Err(err);
+                }
+            }
+        };
+    }
+}
+
+/// (panic mode) On error, logs it in the state and skips to just past the synchronisation parser, giving `None` so parsing continues
+pub const fn recover_until<Iter: Iterator + Checkpoint + ErrorLog<Err>, Err, T, S>(
+    parser: parser![Iter, Err, T],
+    sync: parser![Iter, Err, S]
+)
+    -> parser![Iter, Err, Option<T>]
+{
+    move |iter| match parser(iter) {
+        Result::Ok(t) => Result::Ok(Option::Some(t)),
+        Result::Err(err) => {
+            iter.log_error(err);
+            skip_until(& sync)(iter).map(|_s| Option::None)
+        }
+    }
+}
+
 /// The opposite of `recover_with`; starts the error path if the result type pattern `Err`
 pub const fn flatten_errors<Iter, Err, T>(
     parser: parser![Iter, Err, Result<T, Err>]
@@ -112,12 +168,79 @@ pub const fn use_lst_err<Iter, Err, T>(
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ParseError<Info>{
     Silent,
     Message(String, Info),
     Contextual(String, Info, Box<Self>),
     ErrBundle(Vec<Self>),
-    ErrChoice(Vec<Self>)
+    ErrChoice(Vec<Self>),
+    Committed(Box<Self>)
+}
+
+/// Errors that can record having passed a `cut`, after which `otherwise_cut` stops trying alternatives
+pub trait Commit {
+    fn commit(self) -> Self;
+    fn is_committed(&self) -> bool;
+}
+
+impl<Info> Commit for ParseError<Info> {
+    fn commit(self) -> Self {
+        if self.is_committed() { self } else { ParseError::Committed(Box::new(self)) }
+    }
+
+    fn is_committed(&self) -> bool {
+        match self {
+            ParseError::Committed(_err) => true,
+            ParseError::Contextual(_name, _info, err) => err.is_committed(),
+            ParseError::ErrBundle(errs) => errs.iter().any(Commit::is_committed),
+            ParseError::ErrChoice(errs) => errs.iter().any(Commit::is_committed),
+            _ => false
+        }
+    }
+}
+
+impl<Err: Commit> Commit for Vec<Err> {
+    fn commit(self) -> Self {
+        self.into_iter().map(Commit::commit).collect()
+    }
+
+    fn is_committed(&self) -> bool {
+        self.iter().any(Commit::is_committed)
+    }
+}
+
+/// Wraps any error type so that it can record having passed a `cut`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cut<Err> {
+    pub err: Err,
+    pub committed: bool
+}
+
+impl<Err> Cut<Err> {
+    pub const fn new(err: Err) -> Self {
+        Cut { err, committed: false }
+    }
+}
+
+impl<Err> Commit for Cut<Err> {
+    fn commit(mut self) -> Self {
+        self.committed = true;
+        self
+    }
+
+    fn is_committed(&self) -> bool {
+        self.committed
+    }
+}
+
+/// Commits to the current branch: errors past this point are not caught by `otherwise_cut`
+pub const fn cut<Iter, Err: Commit, T>(
+    parser: parser![Iter, Err, T]
+)
+    -> parser![Iter, Err, T]
+{
+    move |iter| parser(iter).map_err(Commit::commit)
 }
 
 pub fn show_error<Info>(
@@ -147,7 +270,87 @@ pub fn show_error<Info>(
                 errs.into_iter().map(|err|
                     format!("{}\n{padding}|-[ in choice ]\n{}|\n", show_error(format!("{padding}| "), show_info, err), padding)
                 ).collect::<String>()
-            )
+            ),
+        ParseError::Committed(err) =>
+            show_error(padding, show_info, *err)
+    }
+}
+
+fn show_error_ref<Info>(
+    padding: String,
+    show_info: & impl Fn(&Info) -> String,
+    parse_err: & ParseError<Info>
+)
+    -> String
+{
+    match parse_err {
+        ParseError::Silent =>
+            format!("{padding}a silent error occured..."),
+        ParseError::Message(name, info) =>
+            format!("{padding}expected {} {}...", name, show_info(info)),
+        ParseError::Contextual(name, info, err) =>
+            format!("{}\n{padding}...whilst parsing {} {}...", show_error_ref(padding.clone(), show_info, err), name, show_info(info)),
+        ParseError::ErrBundle(errs) =>
+            format!(
+                "{}{padding}[ grouped here ]",
+                errs.iter().map(|err|
+                    format!("{}\n{padding}|-[ in error bundle ]\n{}|\n", show_error_ref(format!("{padding}| "), show_info, err), padding)
+                ).collect::<String>()
+            ),
+        ParseError::ErrChoice(errs) =>
+            format!(
+                "{}{padding}[ branching here ]",
+                errs.iter().map(|err|
+                    format!("{}\n{padding}|-[ in choice ]\n{}|\n", show_error_ref(format!("{padding}| "), show_info, err), padding)
+                ).collect::<String>()
+            ),
+        ParseError::Committed(err) =>
+            show_error_ref(padding, show_info, err)
+    }
+}
+
+/// Displays the whole error tree, as `show_error` does, formatting each `Info` with the given function
+pub struct ShowError<'a, Info, F: Fn(&Info) -> String>(pub &'a ParseError<Info>, pub F);
+
+impl<'a, Info, F: Fn(&Info) -> String> Display for ShowError<'a, Info, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(& show_error_ref(String::new(), & self.1, self.0))
+    }
+}
+
+impl<Info> ParseError<Info> {
+    /// Displays the whole error tree using `show_info` for each `Info` (e.g. `|info| format!("on line {}", info.line)`)
+    pub fn display_with<F: Fn(&Info) -> String>(&self, show_info: F) -> ShowError<'_, Info, F> {
+        ShowError(self, show_info)
+    }
+}
+
+/// Shows `Info` as "at {info}"; a `Contextual` frame only shows its own label, the error it wraps is its `source`
+impl<Info: Display> Display for ParseError<Info> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Contextual(name, info, _err) => write!(f, "...whilst parsing {} at {}...", name, info),
+            ParseError::Committed(err) => Display::fmt(err, f),
+            err => Display::fmt(& err.display_with(|info| format!("at {info}")), f)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Info: Debug + Display + 'static> std::error::Error for ParseError<Info> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ParseError::Contextual(_name, _info, err) => Option::Some(err.as_ref()),
+            ParseError::Committed(err) => err.source(),
+            _ => Option::None
+        }
+    }
+}
+
+/// Groups the errors of `otherwise` as a choice, so they can be passed on with `?`
+impl<Info> From<Vec<ParseError<Info>>> for ParseError<Info> {
+    fn from(errs: Vec<ParseError<Info>>) -> Self {
+        ParseError::ErrChoice(errs)
     }
 }
 
@@ -170,6 +373,7 @@ pub fn truncate_parse_err<Info>(
         ParseError::Contextual(name, info, _ctx) => ParseError::Message(name, info),
         ParseError::ErrBundle(errs) => ParseError::ErrBundle(errs.into_iter().map(truncate_parse_err).collect()),
         ParseError::ErrChoice(errs) => ParseError::ErrChoice(errs.into_iter().map(truncate_parse_err).collect()),
+        ParseError::Committed(err) => ParseError::Committed(Box::new(truncate_parse_err(*err)))
     }
 }
 
@@ -201,6 +405,32 @@ pub fn label<Iter, Info, T>(
     )
 }
 
+/// Like `label` but only snapshots the position up front, getting the info on error path
+pub fn label_lazy<Iter: Snapshot, Info, T>(
+    name: String,
+    info_getter: impl Fn(&Iter) -> Info,
+    parser: parser![Iter, ParseError<Info>, T]
+)
+    -> parser![Iter, ParseError<Info>, T]
+{
+    fmap_err_with_state_lazy(
+        move |iter, err| match err {
+            ParseError::Silent => ParseError::Silent,
+            err_ => ParseError::Contextual(name.clone(), info_getter(iter), Box::new(err_))
+        },
+        parser
+    )
+}
+
+/// Builds the error for `combinators::not_followed_by`, naming what was found
+pub fn unexpected<Iter, Info, T: Debug>(
+    info_getter: impl Fn(&Iter) -> Info
+)
+    -> impl Fn(&Iter, T) -> ParseError<Info>
+{
+    move |iter, t| ParseError::Message(format!("anything but {:?}", t), info_getter(iter))
+}
+
 pub const fn display_full_choice<Iter, Info, T>(
     parser: parser![Iter, Vec<ParseError<Info>>, T]
 )
@@ -217,7 +447,7 @@ pub const fn display_full_choice<Iter, Info, T>(
                     }
                 )
                 .collect();
-            if errs.len() != 0 {
+            if !errs.is_empty() {
                 ParseError::ErrChoice(errs)
             } else {
                 ParseError::Silent
@@ -245,7 +475,7 @@ pub const fn display_fst_choice<Iter, Info, T>(
                 .collect();
             errs.reverse();
             if let Some(fst) = errs.pop() {
-                if errs.len() == 0 {
+                if errs.is_empty() {
                     fst
                 } else {
                     errs = errs.into_iter().map(truncate_parse_err).collect();
@@ -278,7 +508,7 @@ pub const fn display_lst_choice<Iter, Info, T>(
                 )
                 .collect();
             if let Some(lst) = errs.pop() {
-                if errs.len() == 0 {
+                if errs.is_empty() {
                     lst
                 } else {
                     errs = errs.into_iter().map(truncate_parse_err).collect();
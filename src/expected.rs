@@ -0,0 +1,134 @@
+use core::fmt::Debug;
+use alloc::{ vec, vec::Vec, string::String, format };
+
+use super::{ parser, errors::{ Commit, ParseError }, state::{ Checkpoint, Position } };
+
+/// An error that keeps only the furthest failure, merging what every alternative expected there (as in Parsec)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expected<Pos, Item> {
+    pub position: Pos,
+    /// Each thing that would have been accepted at `position`, without duplicates
+    pub expected: Vec<String>,
+    /// The item actually found at `position`, or `None` at the end of input
+    pub found: Option<Item>,
+    pub committed: bool
+}
+
+impl<Pos: Ord, Item> Expected<Pos, Item> {
+    pub fn new(position: Pos, expected: String, found: Option<Item>) -> Self {
+        Expected { position, expected: vec![expected], found, committed: false }
+    }
+
+    /// Keeps whichever error got further, joining the expected sets if both stopped at the same position
+    pub fn merge(mut self, mut other: Self) -> Self {
+        let committed: bool = self.committed || other.committed;
+        let mut merged: Self = match self.position.cmp(& other.position) {
+            core::cmp::Ordering::Greater => self,
+            core::cmp::Ordering::Less => other,
+            core::cmp::Ordering::Equal => {
+                for name in other.expected.drain(..) {
+                    if !self.expected.contains(& name) {
+                        self.expected.push(name);
+                    }
+                }
+                if self.found.is_none() {
+                    self.found = other.found;
+                }
+                self
+            }
+        };
+        merged.committed = committed;
+        merged
+    }
+}
+
+impl<Pos, Item> Commit for Expected<Pos, Item> {
+    fn commit(mut self) -> Self {
+        self.committed = true;
+        self
+    }
+
+    fn is_committed(&self) -> bool {
+        self.committed
+    }
+}
+
+/// Builds the `msg` for a builder, naming what was expected and peeking at what was found instead
+/// (the peek clones the state, so pass it to the `_lazy` builders, which only build errors on error path)
+pub fn expecting<Iter: Iterator + Position + Clone>(
+    name: &str
+)
+    -> impl Fn(&Iter) -> Expected<Iter::Pos, Iter::Item>
+where
+    Iter::Pos: Ord
+{
+    let name: String = String::from(name);
+    move |iter| Expected::new(iter.position(), name.clone(), iter.clone().next())
+}
+
+/// (<|>) Collapses the errors of `otherwise` (or `alternative!`, `choice`, ...) into the furthest, merging expected sets
+/// (with no errors, e.g. from an empty `choice`, it expected nothing at the current position)
+pub fn furthest<Iter: Iterator + Position + Checkpoint, T>(
+    parser: parser![Iter, Vec<Expected<Iter::Pos, Iter::Item>>, T]
+)
+    -> parser![Iter, Expected<Iter::Pos, Iter::Item>, T]
+where
+    Iter::Pos: Ord
+{
+    move |iter| parser(iter).map_err(|errs| match errs.into_iter().reduce(Expected::merge) {
+        Option::Some(err) => err,
+        Option::None => {
+            let pre: Iter::Token = iter.save();
+            let found: Option<Iter::Item> = iter.next();
+            iter.restore(pre);
+            Expected { position: Position::position(&*iter), expected: vec![], found, committed: false }
+        }
+    })
+}
+
+fn show_names(
+    expected: &[String]
+)
+    -> String
+{
+    match expected {
+        [] => String::from("nothing"),
+        [name] => name.clone(),
+        names => format!("one of {}", names.join(", "))
+    }
+}
+
+fn show_found<Item: Debug>(
+    found: Option<Item>
+)
+    -> String
+{
+    match found {
+        Option::Some(item) => format!("{:?}", item),
+        Option::None => String::from("end of input")
+    }
+}
+
+/// Describes the error as "expected one of ... at ..., found ..."
+pub fn show_expected<Pos, Item: Debug>(
+    show_pos: & impl Fn(Pos) -> String,
+    err: Expected<Pos, Item>
+)
+    -> String
+{
+    format!("expected {} {}, found {}", show_names(& err.expected), show_pos(err.position), show_found(err.found))
+}
+
+/// Converts to a `ParseError`, e.g. for `text::render_error`, turning the position into the error's info
+pub fn expected_to_parse_error<Pos, Item: Debug, Info>(
+    pos_info: impl FnOnce(Pos) -> Info,
+    err: Expected<Pos, Item>
+)
+    -> ParseError<Info>
+{
+    let err_: ParseError<Info> = ParseError::Message(
+        format!("{}, found {}", show_names(& err.expected), show_found(err.found)),
+        pos_info(err.position)
+    );
+    if err.committed { err_.commit() } else { err_ }
+}
@@ -0,0 +1,169 @@
+use alloc::{ vec, vec::Vec, string::String, format };
+
+use super::{ errors::ParseError, text::TextInfo };
+
+/// A place in the source mentioned by a `Diagnostic`, from a `Contextual` frame
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Location {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub message: String
+}
+
+/// One failure of a `ParseError<TextInfo>`, with the enclosing `Contextual` frames (innermost first) as related locations
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Diagnostic {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+    pub related: Vec<Location>
+}
+
+fn collect_diagnostics(
+    parse_err: & ParseError<TextInfo>,
+    context: &mut Vec<Location>,
+    diagnostics: &mut Vec<Diagnostic>
+)
+{
+    match parse_err {
+        ParseError::Silent => {},
+        ParseError::Message(name, info) => diagnostics.push(Diagnostic {
+            file: info.file.clone(),
+            line: info.line,
+            column: info.index + 1,
+            message: format!("expected {name}"),
+            related: context.iter().rev().cloned().collect()
+        }),
+        ParseError::Contextual(name, info, err) => {
+            context.push(Location {
+                file: info.file.clone(),
+                line: info.line,
+                column: info.index + 1,
+                message: format!("whilst parsing {name}")
+            });
+            collect_diagnostics(err, context, diagnostics);
+            context.pop();
+        },
+        ParseError::ErrBundle(errs) | ParseError::ErrChoice(errs) => for err in errs {
+            collect_diagnostics(err, context, diagnostics);
+        },
+        ParseError::Committed(err) => collect_diagnostics(err, context, diagnostics)
+    };
+}
+
+/// Flattens the error into one `Diagnostic` per `Message`, in the order they appear
+pub fn diagnostics(
+    parse_err: & ParseError<TextInfo>
+)
+    -> Vec<Diagnostic>
+{
+    let mut diagnostics: Vec<Diagnostic> = vec![];
+    collect_diagnostics(parse_err, &mut vec![], &mut diagnostics);
+    diagnostics
+}
+
+fn json_string(
+    s: &str
+)
+    -> String
+{
+    let mut out: String = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out += "\\\"",
+            '\\' => out += "\\\\",
+            '\n' => out += "\\n",
+            '\r' => out += "\\r",
+            '\t' => out += "\\t",
+            c if (c as u32) < 0x20 => out += & format!("\\u{:04x}", c as u32),
+            c => out.push(c)
+        };
+    }
+    out.push('"');
+    out
+}
+
+fn json_location(
+    location: & Location
+)
+    -> String
+{
+    format!(
+        "{{\"file\":{},\"line\":{},\"column\":{},\"message\":{}}}",
+        json_string(& location.file),
+        location.line,
+        location.column,
+        json_string(& location.message)
+    )
+}
+
+/// Writes one JSON object per diagnostic, each on its own line
+pub fn to_json_lines(
+    parse_err: & ParseError<TextInfo>
+)
+    -> String
+{
+    diagnostics(parse_err)
+        .iter()
+        .map(|diagnostic| format!(
+            "{{\"file\":{},\"line\":{},\"column\":{},\"message\":{},\"related\":[{}]}}\n",
+            json_string(& diagnostic.file),
+            diagnostic.line,
+            diagnostic.column,
+            json_string(& diagnostic.message),
+            diagnostic.related.iter().map(json_location).collect::<Vec<String>>().join(",")
+        ))
+        .collect()
+}
+
+fn sarif_location(
+    file: &str,
+    line: usize,
+    column: usize
+)
+    -> String
+{
+    format!(
+        "\"physicalLocation\":{{\"artifactLocation\":{{\"uri\":{}}},\"region\":{{\"startLine\":{},\"startColumn\":{}}}}}",
+        json_string(file),
+        line,
+        column
+    )
+}
+
+/// Writes a SARIF 2.1.0 log with a single run of `tool`, reporting each diagnostic as an error result
+pub fn to_sarif(
+    tool: &str,
+    parse_err: & ParseError<TextInfo>
+)
+    -> String
+{
+    let results: Vec<String> = diagnostics(parse_err)
+        .iter()
+        .map(|diagnostic| format!(
+            "{{\"level\":\"error\",\"message\":{{\"text\":{}}},\"locations\":[{{{}}}],\"relatedLocations\":[{}]}}",
+            json_string(& diagnostic.message),
+            sarif_location(& diagnostic.file, diagnostic.line, diagnostic.column),
+            diagnostic.related
+                .iter()
+                .enumerate()
+                .map(|(id, location)| format!(
+                    "{{\"id\":{},{},\"message\":{{\"text\":{}}}}}",
+                    id,
+                    sarif_location(& location.file, location.line, location.column),
+                    json_string(& location.message)
+                ))
+                .collect::<Vec<String>>()
+                .join(",")
+        ))
+        .collect();
+    format!(
+        "{{\"$schema\":\"https://json.schemastore.org/sarif-2.1.0.json\",\"version\":\"2.1.0\",\"runs\":[{{\"tool\":{{\"driver\":{{\"name\":{}}}}},\"results\":[{}]}}]}}",
+        json_string(tool),
+        results.join(",")
+    )
+}
@@ -1,5 +1,8 @@
-#![feature(iter_next_chunk)]
-#![feature(decl_macro)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+#![cfg_attr(feature = "nightly", feature(iter_next_chunk))]
+#![allow(clippy::type_complexity)]
+
+extern crate alloc;
 
 pub mod monadic;
 pub mod errors;
@@ -8,24 +11,43 @@ pub mod combinators;
 pub mod utils;
 pub mod state;
 pub mod text;
+pub mod traits;
+pub mod boxed;
+pub mod rule;
+pub mod memo;
+pub mod slice;
+pub mod expected;
+pub mod export;
 
 // parser: impl Fn(&mut: Iter) -> Result<Res, Err>
 
-pub macro parser {
+#[macro_export]
+macro_rules! parser {
     ($iter:ty, $err:ty, $t:ty) => (impl Fn(&mut $iter) -> Result<$t, $err>)
 }
 
 // <|>
-pub macro alternative {
-    ($x:expr) => ($crate::errors::wrap_err($x)),
+#[macro_export]
+macro_rules! alternative {
+    ($x:expr) => ($crate::errors::wrap_err($x));
     ($x:expr, $($xs:expr),+) => (
         $crate::monadic::otherwise($crate::errors::wrap_err($x), $crate::alternative!($($xs),+))
     )
 }
 
+// <|> (stopping at a cut)
+#[macro_export]
+macro_rules! alternative_cut {
+    ($x:expr) => ($crate::errors::wrap_err($x));
+    ($x:expr, $($xs:expr),+) => (
+        $crate::monadic::otherwise_cut($crate::errors::wrap_err($x), $crate::alternative_cut!($($xs),+))
+    )
+}
+
 // <*
-pub macro first {
-    ($x:expr) => ($x),
+#[macro_export]
+macro_rules! first {
+    ($x:expr) => ($x);
     ($x:expr, $($xs:expr),+) => (
         $crate::monadic::fmap2(
             |x0, _x1| x0,
@@ -36,8 +58,9 @@ pub macro first {
 }
 
 // *>
-pub macro last {
-    ($x:expr) => ($x),
+#[macro_export]
+macro_rules! last {
+    ($x:expr) => ($x);
     ($x:expr, $($xs:expr),+) => (
         $crate::monadic::fmap2(
             |_x0, x1| x1,
@@ -48,24 +71,56 @@ pub macro last {
 }
 
 // *> <*
-pub macro select {
+#[macro_export]
+macro_rules! select {
     ($($xs:expr),+, => $y:expr, $($zs:expr),+) => (
         $crate::first!($crate::last!($($xs),+, $y), $($zs),+)
-    ),
+    );
     ($($xs:expr),+, => $y:expr) => (
         $crate::last!($($xs),+, $y)
-    ),
+    );
     (=> $y:expr, $($zs:expr),+) => (
         $crate::first!($y, $($zs),+)
-    ),
+    );
     (=> $y:expr) => (
         ($y)
     )
 }
 
+// <*> over a tuple
+#[macro_export]
+macro_rules! seq {
+    ($($xs:expr),+ $(,)?) => (
+        $crate::monadic::seq(($($xs,)+))
+    )
+}
+
+// do
+#[macro_export]
+macro_rules! parse_do {
+    (@steps $iter:ident; pure $y:expr) => (Result::Ok($y));
+    (@steps $iter:ident; let $x:pat = $p:expr; $($rest:tt)+) => ({
+        let $x = match ($p)($iter) {
+            Result::Ok(t) => t,
+            Result::Err(err) => return Result::Err(err)
+        };
+        $crate::parse_do!(@steps $iter; $($rest)+)
+    });
+    (@steps $iter:ident; $p:expr; $($rest:tt)+) => ({
+        if let Result::Err(err) = ($p)($iter) {
+            return Result::Err(err);
+        }
+        $crate::parse_do!(@steps $iter; $($rest)+)
+    });
+    (@steps $iter:ident; $p:expr) => (($p)($iter));
+    ($($steps:tt)+) => (
+        move |iter: &mut _| $crate::parse_do!(@steps iter; $($steps)+)
+    )
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{*, state::CountIter};
+    use crate::{*, state::CountIter, traits::Parser};
 
     macro_rules! count {
         ($iter:expr) => (state::CountIter{
@@ -80,6 +135,12 @@ mod tests {
         );
     }
 
+    macro_rules! cut_msg {
+        () => (
+            |iter_: & CountIter<std::str::Chars>| errors::Cut::new(iter_.index)
+        );
+    }
+
     #[test]
     fn parse_pure() {
         let mut iter = count!("-".chars());
@@ -359,4 +420,1130 @@ mod tests {
         assert_eq!(Some('-'), iter.next());
         assert_eq!(None, iter.next());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn parse_map_a_on_a() {
+        let mut iter = count!("a-".chars());
+        let res: Result<char, usize> = builders::expect(['a'], msg!())
+            .map(|[a]| a)
+            .parse(&mut iter);
+
+        assert_eq!(Ok('a'), res); // expect success
+
+        assert_eq!(Some('-'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_a_or_b_on_b() {
+        let mut iter = count!("b-".chars());
+        let res: Result<[char; 1], Vec<usize>> = errors::wrap_err(builders::expect(['a'], msg!()))
+            .try_parse()
+            .or(errors::wrap_err(builders::expect(['b'], msg!())))
+            .parse(&mut iter);
+
+        assert_eq!(Ok(['b']), res); // expect success
+
+        assert_eq!(Some('-'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_a_bind_b_on_ab() {
+        let mut iter = count!("ab-".chars());
+        let res: Result<([char; 1], [char; 1]), usize> = builders::expect(['a'], msg!())
+            .bind(|a| builders::expect(['b'], msg!()).map(move |b| (a, b)))
+            .parse(&mut iter);
+
+        assert_eq!(Ok((['a'], ['b'])), res); // expect success
+
+        assert_eq!(Some('-'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_boxed_rules_on_b() {
+        let rules: Vec<boxed::BoxedParser<CountIter<std::str::Chars>, usize, char>> = ['a', 'b']
+            .into_iter()
+            .map(|c| match c {
+                'a' => boxed::boxed(monadic::fmap(|_| 'a', builders::expect(['a'], msg!()))),
+                _ => builders::expect([c], msg!()).map(|[c]| c).boxed()
+            })
+            .collect();
+        let mut iter = count!("b-".chars());
+        let res: Result<char, usize> = rules[1](&mut iter);
+
+        assert_eq!(Ok('b'), res); // expect success
+
+        assert_eq!(Some('-'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_shared_a_a_on_aa() {
+        let a = builders::expect(['a'], msg!()).shared();
+        let mut iter = count!("aa-".chars());
+        let res: Result<([char; 1], [char; 1]), usize> = monadic::fmap2(
+            |a0, a1| (a0, a1),
+            a.clone().into_fn(),
+            a.by_ref()
+        )(&mut iter);
+
+        assert_eq!(Ok((['a'], ['a'])), res); // expect success
+
+        assert_eq!(Some('-'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_recursive_nest_on_nest() {
+        let nest = rule::recursive(|nest: rule::Rule<CountIter<std::str::Chars>, usize, usize>| errors::use_fst_err(
+            monadic::otherwise(
+                errors::wrap_err(select!(
+                    errors::try_parse(builders::expect(['('], msg!())),
+                    => nest.map(|depth| depth + 1),
+                    builders::expect([')'], msg!())
+                )),
+                monadic::pure(|| 0)
+            )
+        ));
+        let mut iter = count!("(())-".chars());
+        let res: Result<usize, usize> = nest(&mut iter);
+
+        assert_eq!(Ok(2), res); // expect success
+
+        assert_eq!(Some('-'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_mutual_rules_on_abab() {
+        let a: rule::Rule<CountIter<std::str::Chars>, usize, usize> = rule::Rule::new();
+        let b: rule::Rule<CountIter<std::str::Chars>, usize, usize> = rule::Rule::new();
+        a.define(errors::use_fst_err(monadic::otherwise(
+            errors::wrap_err(last!(errors::try_parse(builders::expect(['a'], msg!())), b.clone().map(|n| n + 1))),
+            monadic::pure(|| 0)
+        )));
+        b.define(errors::use_fst_err(monadic::otherwise(
+            errors::wrap_err(last!(errors::try_parse(builders::expect(['b'], msg!())), a.downgrade().map(|n| n + 1))),
+            monadic::pure(|| 0)
+        )));
+        let mut iter = count!("abab-".chars());
+        let res: Result<usize, usize> = a.parse(&mut iter);
+
+        assert_eq!(Ok(4), res); // expect success
+
+        assert_eq!(Some('-'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_memo_a_on_ay() {
+        let runs = std::cell::Cell::new(0);
+        let msg = |iter_: & memo::MemoIter<std::str::Chars>| iter_.index;
+        let a = boxed::shared(memo::memo(monadic::fmap(
+            |a| { runs.set(runs.get() + 1); a },
+            builders::expect(['a'], msg)
+        )));
+        let mut iter = memo::MemoIter::new("ay-".chars());
+        let res: Result<[char; 1], usize> = errors::use_lst_err(monadic::otherwise(
+            errors::wrap_err(errors::try_parse(first!(a.by_ref(), builders::expect(['x'], msg)))),
+            errors::wrap_err(first!(a.by_ref(), builders::expect(['y'], msg)))
+        ))(&mut iter);
+
+        assert_eq!(Ok(['a']), res); // expect success
+        assert_eq!(1, runs.get()); // expect one run
+
+        assert_eq!(Some('-'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_left_recursive_sub_on_sub() {
+        let msg = |iter_: & memo::MemoIter<std::str::Chars>| iter_.index;
+        let expr = rule::recursive(|expr: rule::Rule<memo::MemoIter<std::str::Chars>, Vec<usize>, i64>| memo::memo_left(
+            |iter_| vec![iter_.index],
+            monadic::otherwise(
+                errors::try_parse(monadic::fmap3(
+                    |x, _, [y]| x - y.to_digit(10).unwrap() as i64,
+                    expr.into_fn(),
+                    errors::wrap_err(builders::expect(['-'], msg)),
+                    errors::wrap_err(builders::predicate(|[c]: &[char; 1]| c.is_ascii_digit(), msg))
+                )),
+                errors::wrap_err(builders::predicate(|[c]: &[char; 1]| c.is_ascii_digit(), msg)
+                    .map(|[c]| c.to_digit(10).unwrap() as i64))
+            )
+        ));
+        let mut iter = memo::MemoIter::new("9-3-2;".chars());
+        let res: Result<i64, Vec<usize>> = expr(&mut iter);
+
+        assert_eq!(Ok(4), res); // expect success
+
+        assert_eq!(Some(';'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    fn arith<'a>() -> impl Fn(&mut CountIter<std::str::Chars<'a>>) -> Result<String, errors::ParseError<usize>> + 'a {
+        use combinators::{ Assoc, Operator, Fold };
+        let info = |iter_: & CountIter<std::str::Chars>| iter_.index;
+        let op = move |c: char| boxed::boxed(
+            builders::expect([c], move |iter_: & CountIter<std::str::Chars>| errors::ParseError::Message(format!("{c}"), iter_.index))
+                .map(|[c]| c)
+        );
+        combinators::expression(
+            builders::predicate(|[c]: &[char; 1]| c.is_ascii_digit(), |iter_: & CountIter<std::str::Chars>| errors::ParseError::Message(String::from("digit"), iter_.index))
+                .map(|[c]| format!("{c}"))
+                .label(String::from("atom"), info),
+            vec![
+                Operator::Prefix(4, op('-')),
+                Operator::Infix(Assoc::Left, 2, op('+')),
+                Operator::Infix(Assoc::Left, 3, op('*')),
+                Operator::Infix(Assoc::Right, 5, op('^')),
+                Operator::Infix(Assoc::None, 1, op('=')),
+                Operator::Postfix(6, op('!')),
+                Operator::Ternary(0, op('?'), op(':'))
+            ],
+            |fold| match fold {
+                Fold::Prefix(o, x) => format!("({o}{x})"),
+                Fold::Infix(x, o, y) => format!("({x}{o}{y})"),
+                Fold::Postfix(x, o) => format!("({x}{o})"),
+                Fold::Ternary(x, o0, y, o1, z) => format!("({x}{o0}{y}{o1}{z})")
+            },
+            info
+        )
+    }
+
+    #[test]
+    fn parse_expression_on_arith() {
+        let mut iter = count!("-1+2*3!^4^5+6;".chars());
+        let res: Result<String, errors::ParseError<usize>> = arith()(&mut iter);
+
+        assert_eq!(Ok(String::from("(((-1)+(2*((3!)^(4^5))))+6)")), res.map_err(|_err| ())); // expect success
+
+        assert_eq!(Some(';'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_expression_on_ternary() {
+        let mut iter = count!("1=2?3:4?5:6;".chars());
+        let res: Result<String, errors::ParseError<usize>> = arith()(&mut iter);
+
+        assert_eq!(Ok(String::from("((1=2)?3:(4?5:6))")), res.map_err(|_err| ())); // expect success
+
+        assert_eq!(Some(';'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_expression_on_chained_nonassoc() {
+        let mut iter = count!("1=2=3;".chars());
+        let res: Result<String, errors::ParseError<usize>> = arith()(&mut iter);
+
+        assert!(matches!(res, Err(errors::ParseError::Message(_, 3)))); // expect failure
+    }
+
+    macro_rules! err_msg {
+        ($name:expr) => (
+            |iter_: & CountIter<std::str::Chars>| errors::ParseError::Message(String::from($name), iter_.index)
+        );
+    }
+
+    #[test]
+    fn parse_ab_or_ac_on_ac() {
+        let mut iter = count!("ac-".chars());
+        let res: Result<[char; 1], Vec<errors::ParseError<usize>>> = alternative!(
+            errors::try_parse(last!(builders::expect(['a'], err_msg!("a")), builders::expect(['b'], err_msg!("b")))),
+            last!(builders::expect(['a'], err_msg!("a")), builders::expect(['c'], err_msg!("c")))
+        )(&mut iter);
+
+        assert_eq!(Ok(['c']), res.map_err(|_errs| ())); // expect success
+
+        assert_eq!(Some('-'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_a_cut_b_or_ac_on_ac() {
+        let mut iter = count!("ac-".chars());
+        let res: Result<[char; 1], Vec<errors::ParseError<usize>>> = alternative_cut!(
+            errors::try_parse(last!(builders::expect(['a'], err_msg!("a")), errors::cut(builders::expect(['b'], err_msg!("b"))))),
+            last!(builders::expect(['a'], err_msg!("a")), builders::expect(['c'], err_msg!("c")))
+        )(&mut iter);
+
+        assert!(matches!(res.as_ref().map_err(Vec::as_slice), Err([errors::ParseError::Committed(_)]))); // expect committed failure
+
+        assert_eq!(Some('a'), iter.next());
+        assert_eq!(Some('c'), iter.next());
+    }
+
+    #[test]
+    fn parse_recognize_ident_on_str() {
+        let source: String = String::from("héllo wörld");
+        let mut iter = slice::StrIter::new(&source);
+        let res: Result<&str, usize> = slice::recognize(combinators::most(errors::try_parse(
+            builders::predicate(|[c]: &[char; 1]| c.is_alphabetic(), |iter_: & slice::StrIter| iter_.offset)
+        )))(&mut iter);
+
+        assert_eq!(Ok("héllo"), res); // expect success
+
+        assert_eq!(Some(' '), iter.next());
+        assert_eq!(7, iter.offset);
+    }
+
+    #[test]
+    fn parse_consumed_on_slice() {
+        let source: [u8; 4] = [1, 2, 3, 4];
+        let mut iter = slice::SliceIter::new(&source);
+        let res: Result<(&[u8], [u8; 2]), usize> = slice::consumed(
+            builders::expect([1, 2], |iter_: & slice::SliceIter<u8>| iter_.offset)
+        )(&mut iter);
+
+        assert_eq!(Ok((&source[..2], [1, 2])), res); // expect success
+
+        assert_eq!(Some(3), iter.next());
+        assert_eq!(Some(4), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_try_stack_on_b() {
+        let mut iter = state::StackIter::with_stack("b-".chars(), vec!['(', '[']);
+        let msg = |iter_: & std::str::Chars, _a, _b| iter_.as_str().len();
+        let res: Result<[char; 1], usize> = errors::try_parse(last!(
+            state::stack_pop('[', msg),
+            state::stack_pop('(', msg),
+            state::stack_push('{'),
+            builders::expect(['a'], |iter_: & state::StackIter<std::str::Chars, char>| iter_.iter.as_str().len())
+        ))(&mut iter);
+
+        assert_eq!(Err(2), res); // expect failure
+
+        assert_eq!(['(', '['], iter.stack());
+        assert_eq!(Some('b'), iter.next());
+        assert_eq!(Some('-'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_abc_on_abc_lazily() {
+        let calls = std::cell::Cell::new(0);
+        let mut iter = count!("abc-".chars());
+        let res: Result<[char; 3], usize> = builders::expect_lazy(
+            ['a', 'b', 'c'],
+            |iter_: & CountIter<std::str::Chars>| { calls.set(calls.get() + 1); iter_.index }
+        )(&mut iter);
+
+        assert_eq!(Ok(['a', 'b', 'c']), res); // expect success
+        assert_eq!(0, calls.get()); // expect no error built
+
+        assert_eq!(Some('-'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_a_on_a_peekable() {
+        let mut iter = "a-".chars().peekable();
+        let res: Result<[char; 1], ()> = builders::expect(['a'], |_iter_: & std::iter::Peekable<std::str::Chars>| ())(&mut iter);
+
+        assert_eq!(Ok(['a']), res); // expect success
+
+        assert_eq!(Some('-'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_label_lazy_on_ab() {
+        let mut iter = count!("ab".chars());
+        let res: Result<[char; 2], errors::ParseError<usize>> = errors::label_lazy(
+            String::from("aa"),
+            |iter_: & CountIter<std::str::Chars>| iter_.index,
+            last!(builders::expect_lazy(['a'], err_msg!("a")), builders::expect_lazy(['a', 'a'], err_msg!("a")))
+        )(&mut iter);
+
+        assert!(matches!(res, Err(errors::ParseError::Contextual(_, 0, _)))); // expect failure labelled before parsing
+
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_label_stack_on_b() {
+        let mut iter = state::StackIter::with_stack("b-".chars(), vec!['(', '[']);
+        let msg = |_iter_: & std::str::Chars, _a, _b| errors::ParseError::Silent;
+        let res: Result<[char; 1], errors::ParseError<usize>> = errors::label(
+            String::from("group"),
+            |iter_: & state::StackIter<std::str::Chars, char>| iter_.stack().len(),
+            last!(
+                state::stack_pop('[', msg),
+                state::stack_pop('(', msg),
+                builders::expect(['a'], |_iter_: & state::StackIter<std::str::Chars, char>| errors::ParseError::Message(String::from("a"), 0))
+            )
+        )(&mut iter);
+
+        assert!(matches!(res, Err(errors::ParseError::Contextual(_, 2, _)))); // expect failure labelled before parsing
+
+        assert!(iter.stack().is_empty());
+        assert_eq!(Some('-'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_most_try_a_till_c_on_million_a() {
+        let source: String = "a".repeat(1_000_000) + "c-";
+        let mut iter = count!(source.chars());
+        let res: Result<(Vec<[char; 1]>, [char; 1]), Vec<usize>> = combinators::most_till(
+            errors::try_parse(builders::expect(['a'], msg!())),
+            builders::expect(['c'], msg!())
+        )(&mut iter);
+
+        assert_eq!(Ok((vec![['a']; 1_000_000], ['c'])), res); // expect success
+
+        assert_eq!(Some('-'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_least_a_till_try_c_on_million_a() {
+        let source: String = "a".repeat(1_000_000) + "c-";
+        let mut iter = count!(source.chars());
+        let res: Result<(Vec<[char; 1]>, [char; 1]), Vec<usize>> = combinators::least_till(
+            builders::expect(['a'], msg!()),
+            errors::try_parse(builders::expect(['c'], msg!()))
+        )(&mut iter);
+
+        assert_eq!(Ok((vec![['a']; 1_000_000], ['c'])), res); // expect success
+
+        assert_eq!(Some('-'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_repeat_a_on_aaaa() {
+        let mut iter = count!("aaaa-".chars());
+        let res: Result<Vec<[char; 1]>, Vec<usize>> = combinators::repeat(2..=3, builders::expect(['a'], msg!()))(&mut iter);
+
+        assert_eq!(Ok(vec![['a'], ['a'], ['a']]), res); // expect success
+
+        assert_eq!(Some('a'), iter.next());
+        assert_eq!(Some('-'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_count_a_on_ab() {
+        let mut iter = count!("ab-".chars());
+        let res: Result<Vec<[char; 1]>, Vec<usize>> = combinators::count(2, builders::expect(['a'], msg!()))(&mut iter);
+
+        assert_eq!(Err(vec![1]), res); // expect failure
+
+        assert_eq!(Some('-'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_sep_by_a_on_a_a_a() {
+        let mut iter = count!("a,a,a-".chars());
+        let res: Result<Vec<[char; 1]>, Vec<usize>> = combinators::sep_by(
+            builders::expect(['a'], msg!()),
+            errors::try_parse(builders::expect([','], msg!()))
+        )(&mut iter);
+
+        assert_eq!(Ok(vec![['a'], ['a'], ['a']]), res); // expect success
+
+        assert_eq!(Some('-'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_sep_by_a_on_a_b() {
+        let mut iter = count!("a,b-".chars());
+        let res: Result<Vec<[char; 1]>, Vec<usize>> = combinators::sep_by(
+            builders::expect(['a'], msg!()),
+            errors::try_parse(builders::expect([','], msg!()))
+        )(&mut iter);
+
+        assert_eq!(Err(vec![2]), res); // expect failure
+
+        assert_eq!(Some('-'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_sep_end_by_a_on_a_a_() {
+        let mut iter = count!("a,a,-".chars());
+        let res: Result<Vec<[char; 1]>, Vec<usize>> = combinators::sep_end_by(
+            errors::try_parse(builders::expect(['a'], msg!())),
+            errors::try_parse(builders::expect([','], msg!()))
+        )(&mut iter);
+
+        assert_eq!(Ok(vec![['a'], ['a']]), res); // expect success
+
+        assert_eq!(Some('-'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_between_a_on_a_in_parens() {
+        let mut iter = count!("(a)-".chars());
+        let res: Result<[char; 1], Vec<usize>> = combinators::between(
+            builders::expect(['('], msg!()),
+            builders::expect(['a'], msg!()),
+            builders::expect([')'], msg!())
+        )(&mut iter);
+
+        assert_eq!(Ok(['a']), res); // expect success
+
+        assert_eq!(Some('-'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_most_with_err_a_on_aab() {
+        let mut iter = count!("aab-".chars());
+        let res: Result<(Vec<[char; 1]>, usize), usize> = combinators::most_with_err(builders::expect(['a'], msg!()))(&mut iter);
+
+        assert_eq!(Ok((vec![['a'], ['a']], 2)), res); // expect success
+
+        assert_eq!(Some('-'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_most_checked_pure_on_a() {
+        let mut iter = count!("a-".chars());
+        let res: Result<(Vec<()>, usize), usize> = combinators::most_checked(monadic::pure(|| ()), |_iter_| usize::MAX)(&mut iter);
+
+        assert_eq!(Err(usize::MAX), res); // expect failure
+
+        assert_eq!(Some('a'), iter.next());
+        assert_eq!(Some('-'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_if_not_followed_by_ident_on_if() {
+        let mut iter = count!("if(".chars());
+        let res: Result<[char; 2], errors::ParseError<usize>> = first!(
+            builders::expect(['i', 'f'], err_msg!("if")),
+            combinators::not_followed_by(
+                builders::predicate(|[c]: &[char; 1]| c.is_alphanumeric(), err_msg!("identifier character")),
+                errors::unexpected(|iter_: & CountIter<std::str::Chars>| iter_.index)
+            )
+        )(&mut iter);
+
+        assert_eq!(Ok(['i', 'f']), res.map_err(|_err| ())); // expect success
+
+        assert_eq!(Some('('), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_if_not_followed_by_ident_on_iffy() {
+        let mut iter = count!("iffy".chars());
+        let res: Result<[char; 2], errors::ParseError<usize>> = first!(
+            builders::expect(['i', 'f'], err_msg!("if")),
+            combinators::not_followed_by(
+                builders::predicate(|[c]: &[char; 1]| c.is_alphanumeric(), err_msg!("identifier character")),
+                errors::unexpected(|iter_: & CountIter<std::str::Chars>| iter_.index)
+            )
+        )(&mut iter);
+
+        assert_eq!(
+            "expected anything but ['f'] at 2...",
+            errors::show_error(String::new(), & |info| format!("at {info}"), res.unwrap_err())
+        ); // expect failure
+
+        assert_eq!(Some('f'), iter.next());
+        assert_eq!(Some('y'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_followed_by_a_on_a() {
+        let mut iter = count!("a-".chars());
+        let res: Result<(), usize> = combinators::followed_by(builders::expect(['a'], msg!()))(&mut iter);
+
+        assert_eq!(Ok(()), res); // expect success
+
+        assert_eq!(Some('a'), iter.next());
+        assert_eq!(Some('-'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_optional_a_on_b() {
+        let mut iter = count!("b".chars());
+        let res: Result<Option<[char; 1]>, usize> = combinators::optional(errors::try_parse(builders::expect(['a'], msg!())))(&mut iter);
+
+        assert_eq!(Ok(None), res); // expect success
+
+        assert_eq!(Some('b'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_choice_on_c() {
+        let mut iter = count!("c-".chars());
+        let parsers: Vec<boxed::BoxedParser<CountIter<std::str::Chars>, usize, char>> = ['a', 'b', 'c']
+            .into_iter()
+            .map(|c| errors::try_parse(builders::expect([c], msg!())).map(|[c]| c).boxed())
+            .collect();
+        let res: Result<char, Vec<usize>> = combinators::choice(parsers)(&mut iter);
+
+        assert_eq!(Ok('c'), res); // expect success
+
+        assert_eq!(Some('-'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_choice_on_d() {
+        let mut iter = count!("d".chars());
+        let parsers: [boxed::BoxedParser<CountIter<std::str::Chars>, usize, char>; 2] = [
+            errors::try_parse(builders::expect(['a'], msg!())).map(|[c]| c).boxed(),
+            errors::try_parse(builders::expect(['b'], msg!())).map(|[c]| c).boxed()
+        ];
+        let res: Result<char, Vec<usize>> = combinators::choice(parsers)(&mut iter);
+
+        assert_eq!(Err(vec![0, 0]), res); // expect failure
+
+        assert_eq!(Some('d'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_permutation_on_ca() {
+        let mut iter = count!("ca-".chars());
+        let parsers: Vec<(bool, boxed::BoxedParser<CountIter<std::str::Chars>, usize, char>)> = ['a', 'b', 'c']
+            .into_iter()
+            .map(|c| (c == 'b', errors::try_parse(builders::expect([c], msg!())).map(|[c]| c).boxed()))
+            .collect();
+        let res: Result<Vec<Option<char>>, Vec<usize>> = combinators::permutation(parsers)(&mut iter);
+
+        assert_eq!(Ok(vec![Some('a'), None, Some('c')]), res); // expect success
+
+        assert_eq!(Some('-'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_permutation_on_b() {
+        let mut iter = count!("b".chars());
+        let parsers: Vec<(bool, boxed::BoxedParser<CountIter<std::str::Chars>, usize, char>)> = ['a', 'b']
+            .into_iter()
+            .map(|c| (false, errors::try_parse(builders::expect([c], msg!())).map(|[c]| c).boxed()))
+            .collect();
+        let res: Result<Vec<Option<char>>, Vec<usize>> = combinators::permutation(parsers)(&mut iter);
+
+        assert_eq!(Err(vec![1]), res); // expect failure
+
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_chainl1_sub_on_8_3_2() {
+        let mut iter = count!("8-3-2;".chars());
+        let res: Result<i32, Vec<usize>> = combinators::chainl1(
+            builders::predicate(|[c]: &[char; 1]| c.is_ascii_digit(), msg!()).map(|[c]| c as i32 - '0' as i32),
+            errors::try_parse(builders::expect(['-'], msg!())).map(|_| |a: i32, b: i32| a - b)
+        )(&mut iter);
+
+        assert_eq!(Ok(3), res); // expect success
+
+        assert_eq!(Some(';'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_chainr1_sub_on_8_3_2() {
+        let mut iter = count!("8-3-2;".chars());
+        let res: Result<i32, Vec<usize>> = combinators::chainr1(
+            builders::predicate(|[c]: &[char; 1]| c.is_ascii_digit(), msg!()).map(|[c]| c as i32 - '0' as i32),
+            errors::try_parse(builders::expect(['-'], msg!())).map(|_| |a: i32, b: i32| a - b)
+        )(&mut iter);
+
+        assert_eq!(Ok(7), res); // expect success
+
+        assert_eq!(Some(';'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_chainl1_sub_on_8_dangling() {
+        let mut iter = count!("8-;".chars());
+        let res: Result<i32, Vec<usize>> = combinators::chainl1(
+            builders::predicate(|[c]: &[char; 1]| c.is_ascii_digit(), msg!()).map(|[c]| c as i32 - '0' as i32),
+            errors::try_parse(builders::expect(['-'], msg!())).map(|_| |a: i32, b: i32| a - b)
+        )(&mut iter);
+
+        assert_eq!(Err(vec![2]), res); // expect failure
+
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_fold_many_digits_on_123() {
+        let mut iter = count!("123x".chars());
+        let res: Result<u32, usize> = combinators::fold_many(
+            errors::try_parse(builders::predicate(|[c]: &[char; 1]| c.is_ascii_digit(), msg!())),
+            || 0,
+            |n, [c]| n * 10 + c.to_digit(10).unwrap()
+        )(&mut iter);
+
+        assert_eq!(Ok(123), res); // expect success
+
+        assert_eq!(Some('x'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_seq_on_abcdefghijkl() {
+        let mut iter = count!("abcdefghijkl-".chars());
+        let res: Result<(char, [char; 1], [char; 1], [char; 1], [char; 1], [char; 1], [char; 1], [char; 1], [char; 1], [char; 1], [char; 1], char), usize> = seq!(
+            builders::expect(['a'], msg!()).map(|[c]| c),
+            builders::expect(['b'], msg!()),
+            builders::expect(['c'], msg!()),
+            builders::expect(['d'], msg!()),
+            builders::expect(['e'], msg!()),
+            builders::expect(['f'], msg!()),
+            builders::expect(['g'], msg!()),
+            builders::expect(['h'], msg!()),
+            builders::expect(['i'], msg!()),
+            builders::expect(['j'], msg!()),
+            builders::expect(['k'], msg!()),
+            builders::expect(['l'], msg!()).map(|[c]| c)
+        )(&mut iter);
+
+        assert_eq!(Ok(('a', 'l')), res.map(|(a, _b, _c, _d, _e, _f, _g, _h, _i, _j, _k, l)| (a, l))); // expect success
+
+        assert_eq!(Some('-'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_seq_on_ac() {
+        let mut iter = count!("ac".chars());
+        let res: Result<([char; 1], [char; 1]), usize> = monadic::seq((
+            builders::expect(['a'], msg!()),
+            builders::expect(['b'], msg!())
+        ))(&mut iter);
+
+        assert_eq!(Err(1), res); // expect failure
+
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_do_decl_on_x_colon_int() {
+        let mut iter = count!("x:int;".chars());
+        let res: Result<(char, String), usize> = parse_do! {
+            let [name] = builders::predicate(|[c]: &[char; 1]| c.is_alphabetic(), msg!());
+            builders::expect([':'], msg!());
+            let ty = combinators::most(errors::try_parse(builders::predicate(|[c]: &[char; 1]| c.is_alphabetic(), msg!())));
+            pure (name, ty.into_iter().map(|[c]| c).collect())
+        }(&mut iter);
+
+        assert_eq!(Ok(('x', String::from("int"))), res); // expect success
+
+        assert_eq!(Some(';'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_do_dependent_on_2ab() {
+        let mut iter = count!("2ab".chars());
+        let res: Result<Vec<char>, usize> = parse_do! {
+            let [n] = builders::predicate(|[c]: &[char; 1]| c.is_ascii_digit(), msg!());
+            errors::use_fst_err(combinators::count(
+                n.to_digit(10).unwrap() as usize,
+                builders::predicate(|[c]: &[char; 1]| c.is_alphabetic(), msg!()).map(|[c]| c)
+            ))
+        }(&mut iter);
+
+        assert_eq!(Ok(vec!['a', 'b']), res); // expect success
+
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_do_on_x_semicolon() {
+        let mut iter = count!("x;".chars());
+        let res: Result<char, usize> = parse_do! {
+            let [name] = builders::predicate(|[c]: &[char; 1]| c.is_alphabetic(), msg!());
+            builders::expect([':'], msg!());
+            pure name
+        }(&mut iter);
+
+        assert_eq!(Err(1), res); // expect failure
+
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn render_error_on_decl() {
+        let source: &str = "let x = 1;\nlet y 2;\n";
+        let err: errors::ParseError<text::TextInfo> = errors::ParseError::Contextual(
+            String::from("declaration"),
+            text::TextInfo { file: String::from("main.txt"), line: 2, index: 0 },
+            Box::new(errors::ParseError::Message(
+                String::from("'='"),
+                text::TextInfo { file: String::from("main.txt"), line: 2, index: 6 }
+            ))
+        );
+
+        assert_eq!(
+            "error: expected '='\n --> main.txt:2:7\n  |\n2 | let y 2;\n  |       ^\n\
+            note: whilst parsing declaration\n --> main.txt:2:1\n  |\n2 | let y 2;\n  | -\n",
+            text::render_error(&[("main.txt", source)], false, err)
+        );
+    }
+
+    #[test]
+    fn render_error_from_text_iter() {
+        let source: &str = "ab\nac";
+        let mut iter = text::TextIter::new(String::from("main.txt"), source.chars());
+        let res: Result<[char; 4], errors::ParseError<text::TextInfo>> = builders::expect(
+            ['a', 'b', '\n', 'a'],
+            |iter_: & text::TextIter<std::str::Chars>| errors::ParseError::Message(String::from("\"ab a\""), iter_.info.clone())
+        )(&mut iter);
+
+        assert_eq!(Ok(['a', 'b', '\n', 'a']), res.map_err(|_err| ())); // expect success
+
+        let res: Result<[char; 1], errors::ParseError<text::TextInfo>> = builders::expect(
+            ['b'],
+            |iter_: & text::TextIter<std::str::Chars>| errors::ParseError::Message(String::from("'b'"), iter_.info.clone())
+        )(&mut iter);
+
+        assert_eq!(
+            "error: expected 'b'\n --> main.txt:2:2\n  |\n2 | ac\n  |  ^\n",
+            text::render_error(&[("main.txt", source)], false, res.unwrap_err())
+        ); // expect failure
+    }
+
+    #[test]
+    fn parse_furthest_list_on_unclosed() {
+        let mut iter = count!("[1x".chars());
+        let item = || errors::try_parse(builders::predicate_lazy(
+            |[c]: &[char; 1]| c.is_ascii_digit(),
+            expected::expecting("digit")
+        ));
+        let res: Result<char, expected::Expected<usize, char>> = expected::furthest(alternative!(
+            errors::try_parse(last!(
+                builders::expect_lazy(['['], expected::expecting("'['")),
+                item(),
+                expected::furthest(alternative!(
+                    errors::try_parse(builders::expect_lazy([','], expected::expecting("','"))),
+                    errors::try_parse(builders::expect_lazy([']'], expected::expecting("']'")))
+                ))
+            )).map(|_| 'l'),
+            item().map(|[c]| c)
+        ))(&mut iter);
+
+        assert_eq!(
+            "expected one of ',', ']' at 2, found 'x'",
+            expected::show_expected(& |pos| format!("at {pos}"), res.unwrap_err())
+        ); // expect failure
+
+        assert_eq!(Some('['), iter.next());
+    }
+
+    #[test]
+    fn merge_expected_at_same_position() {
+        let a: expected::Expected<usize, char> = expected::Expected::new(3, String::from("','"), Some('x'));
+        let b: expected::Expected<usize, char> = expected::Expected::new(3, String::from("']'"), Some('x'));
+        let c: expected::Expected<usize, char> = expected::Expected::new(1, String::from("digit"), Some('['));
+
+        assert_eq!(
+            expected::Expected { position: 3, expected: vec![String::from("','"), String::from("']'")], found: Some('x'), committed: false },
+            c.merge(a.clone()).merge(b).merge(a)
+        );
+    }
+
+    #[test]
+    fn display_contextual_error() {
+        let err: errors::ParseError<usize> = errors::ParseError::Contextual(
+            String::from("list"),
+            0,
+            Box::new(errors::ParseError::Message(String::from("']'"), 3))
+        );
+
+        assert_eq!("...whilst parsing list at 0...", format!("{err}"));
+        assert_eq!(
+            "expected ']' on 3...\n...whilst parsing list on 0...",
+            format!("{}", err.display_with(|info| format!("on {info}")))
+        );
+        assert_eq!(
+            errors::show_error(String::new(), & |info| format!("on {info}"), err.clone()),
+            format!("{}", err.display_with(|info| format!("on {info}")))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn error_source_walks_contextual() {
+        fn parse_list(source: &str) -> Result<[char; 2], Box<dyn std::error::Error>> {
+            let mut iter = text::TextIter::new(String::from("main.txt"), source.chars());
+            let res = errors::label(
+                String::from("list"),
+                |iter_: & text::TextIter<std::str::Chars>| iter_.info.clone(),
+                builders::expect(
+                    ['[', ']'],
+                    |iter_: & text::TextIter<std::str::Chars>| errors::ParseError::Message(String::from("\"[]\""), iter_.info.clone())
+                )
+            )(&mut iter)?;
+            Ok(res)
+        }
+
+        let err: Box<dyn std::error::Error> = parse_list("[x").unwrap_err();
+
+        assert_eq!("...whilst parsing list at main.txt:1:1...", format!("{err}"));
+        assert_eq!(
+            Some(String::from("expected \"[]\" at main.txt:1:1...")),
+            err.source().map(|err_| format!("{err_}"))
+        );
+    }
+
+    #[test]
+    fn export_json_lines_and_sarif() {
+        let info = |line: usize, index: usize| text::TextInfo { file: String::from("main.txt"), line, index };
+        let err: errors::ParseError<text::TextInfo> = errors::ParseError::Contextual(
+            String::from("list"),
+            info(1, 0),
+            Box::new(errors::ParseError::ErrChoice(vec![
+                errors::ParseError::Message(String::from("','"), info(1, 2)),
+                errors::ParseError::Silent,
+                errors::ParseError::Message(String::from("\"]\""), info(1, 2))
+            ]))
+        );
+
+        assert_eq!(
+            "{\"file\":\"main.txt\",\"line\":1,\"column\":3,\"message\":\"expected ','\",\"related\":[\
+            {\"file\":\"main.txt\",\"line\":1,\"column\":1,\"message\":\"whilst parsing list\"}]}\n\
+            {\"file\":\"main.txt\",\"line\":1,\"column\":3,\"message\":\"expected \\\"]\\\"\",\"related\":[\
+            {\"file\":\"main.txt\",\"line\":1,\"column\":1,\"message\":\"whilst parsing list\"}]}\n",
+            export::to_json_lines(& err)
+        );
+        assert_eq!(
+            "{\"$schema\":\"https://json.schemastore.org/sarif-2.1.0.json\",\"version\":\"2.1.0\",\"runs\":[{\"tool\":{\"driver\":{\"name\":\"nibbler\"}},\"results\":[\
+            {\"level\":\"error\",\"message\":{\"text\":\"expected ','\"},\
+            \"locations\":[{\"physicalLocation\":{\"artifactLocation\":{\"uri\":\"main.txt\"},\"region\":{\"startLine\":1,\"startColumn\":3}}}],\
+            \"relatedLocations\":[{\"id\":0,\"physicalLocation\":{\"artifactLocation\":{\"uri\":\"main.txt\"},\"region\":{\"startLine\":1,\"startColumn\":1}},\
+            \"message\":{\"text\":\"whilst parsing list\"}}]}\
+            ]}]}",
+            export::to_sarif("nibbler", & errors::ParseError::Committed(Box::new(errors::ParseError::Contextual(
+                String::from("list"),
+                info(1, 0),
+                Box::new(errors::ParseError::Message(String::from("','"), info(1, 2)))
+            ))))
+        );
+    }
+
+    #[test]
+    fn parse_statements_recovering_on_a_x_b_yy_c() {
+        let mut iter = state::RecoverIter::new(count!("a;x;b;yy;c;".chars()));
+        let msg = |iter_: & state::RecoverIter<CountIter<std::str::Chars>, usize>| iter_.iter.index;
+        let res: Result<(Vec<Option<[char; 1]>>, ()), Vec<usize>> = combinators::least_till(
+            errors::recover_until(
+                first!(
+                    builders::predicate(|[c]: &[char; 1]| ('a'..='c').contains(c), msg),
+                    builders::expect([';'], msg)
+                ),
+                builders::expect([';'], msg)
+            ),
+            errors::try_parse(builders::eos(msg))
+        )(&mut iter);
+
+        assert_eq!(Ok((vec![Some(['a']), None, Some(['b']), None, Some(['c'])], ())), res); // expect success
+        assert_eq!([2, 6], iter.errors());
+
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_skip_until_semicolon_on_xyz() {
+        let mut iter = count!("xyz".chars());
+        let res: Result<[char; 1], usize> = errors::skip_until(builders::expect([';'], msg!()))(&mut iter);
+
+        assert_eq!(Err(3), res); // expect failure
+
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_most_try_brackets_on_many_brackets_stack() {
+        let source: String = "()".repeat(100_000) + "(]";
+        let mut iter = state::StackIter::new(source.chars());
+        let bracket = |iter_: &mut state::StackIter<std::str::Chars, char>| match iter_.next() {
+            Some('(') => {
+                iter_.push('(');
+                Ok(())
+            },
+            Some(')') => iter_.pop().map(|_| ()).ok_or(0),
+            _ => Err(1)
+        };
+        let res: Result<Vec<()>, usize> = combinators::most(errors::try_parse(bracket))(&mut iter);
+
+        assert_eq!(Ok(vec![(); 200_001]), res); // expect success
+
+        assert_eq!(['('], iter.stack());
+        assert_eq!(Some(']'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_a_or_b_on_b_custom_err() {
+        #[derive(Debug, PartialEq)]
+        enum MyErr { Expected(char) }
+
+        let mut iter = count!("b-".chars());
+        let res: Result<[char; 1], Vec<MyErr>> = alternative!(
+            errors::try_parse(builders::expect(['a'], |_iter_: & CountIter<std::str::Chars>| MyErr::Expected('a'))),
+            builders::expect(['b'], |_iter_: & CountIter<std::str::Chars>| MyErr::Expected('b'))
+        )(&mut iter);
+
+        assert_eq!(Ok(['b']), res); // expect success
+
+        assert_eq!(Some('-'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_do_named_on_x_colon_int() {
+        let mut iter = count!("x:int;".chars());
+        let letter = errors::label(
+            String::from("letter"),
+            msg!(),
+            builders::predicate(|[c]: &[char; 1]| c.is_alphabetic(), |_iter_: & CountIter<std::str::Chars>| errors::ParseError::Silent)
+        );
+        let colon = builders::expect([':'], |_iter_: & CountIter<std::str::Chars>| errors::ParseError::Silent).boxed();
+        let res: Result<(char, usize), errors::ParseError<usize>> = parse_do! {
+            let [name] = letter;
+            colon;
+            let ty = combinators::most(errors::try_parse(& letter));
+            pure (name, ty.len())
+        }(&mut iter);
+
+        assert!(matches!(res, Ok(('x', 3)))); // expect success
+
+        assert_eq!(Some(';'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    #[should_panic(expected = "repeat needs a non-empty range")]
+    fn build_repeat_on_inverted_range() {
+        let (min, max): (usize, usize) = (3, 2);
+        let _parser = combinators::repeat(min..max, builders::expect(['a'], msg!()));
+    }
+
+    #[test]
+    fn parse_sep_by_a_cut_b_on_a_ac() {
+        let mut iter = count!("a,ac-".chars());
+        let res: Result<Vec<[char; 1]>, Vec<errors::Cut<usize>>> = combinators::sep_by_cut(
+            errors::try_parse(last!(builders::expect(['a'], cut_msg!()), errors::cut(builders::expect(['b'], cut_msg!())))),
+            errors::try_parse(builders::expect([','], cut_msg!()))
+        )(&mut iter);
+
+        assert_eq!(Err(vec![errors::Cut { err: 1, committed: true }]), res); // expect committed failure
+
+        assert_eq!(Some('a'), iter.next());
+        assert_eq!(Some(','), iter.next());
+    }
+
+    #[test]
+    fn parse_furthest_empty_choice_on_x() {
+        let mut iter = count!("x".chars());
+        let parsers: Vec<boxed::BoxedParser<CountIter<std::str::Chars>, expected::Expected<usize, char>, char>> = vec![];
+        let res: Result<char, expected::Expected<usize, char>> = expected::furthest(combinators::choice(parsers))(&mut iter);
+
+        assert_eq!(Err(expected::Expected { position: 0, expected: vec![], found: Some('x'), committed: false }), res); // expect failure
+
+        assert_eq!(Some('x'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_try_recovering_a_then_b_on_x_c() {
+        let mut iter = state::RecoverIter::new(count!("x;c".chars()));
+        let msg = |iter_: & state::RecoverIter<CountIter<std::str::Chars>, usize>| iter_.iter.index;
+        let res: Result<[char; 1], usize> = errors::try_parse(last!(
+            errors::recover_until(builders::expect(['a'], msg), builders::expect([';'], msg)),
+            builders::expect(['b'], msg)
+        ))(&mut iter);
+
+        assert_eq!(Err(2), res); // expect failure
+        assert!(iter.errors().is_empty());
+
+        assert_eq!(Some('x'), iter.next());
+        assert_eq!(Some(';'), iter.next());
+    }
+
+    #[test]
+    fn parse_memo_a_interleaved_on_a_and_a() {
+        let runs = std::cell::Cell::new(0);
+        let msg = |iter_: & memo::MemoIter<std::str::Chars>| iter_.index;
+        let a = memo::memo(monadic::fmap(
+            |a| { runs.set(runs.get() + 1); a },
+            builders::expect(['a'], msg)
+        ));
+        let mut iter0 = memo::MemoIter::new("a0".chars());
+        let mut iter1 = memo::MemoIter::new("a1".chars());
+        let start0 = iter0.clone();
+        let start1 = iter1.clone();
+        let res: [Result<[char; 1], usize>; 4] = [a(&mut iter0), a(&mut iter1), a(&mut { start0 }), a(&mut { start1 })];
+
+        assert_eq!([Ok(['a']), Ok(['a']), Ok(['a']), Ok(['a'])], res); // expect success
+        assert_eq!(2, runs.get()); // expect one run per input
+
+        assert_eq!(Some('0'), iter0.next());
+        assert_eq!(Some('1'), iter1.next());
+    }
+
+    #[test]
+    fn parse_try_ab_or_ac_on_ac_peekable() {
+        let mut iter = count!(state::ByClone::new("ac-".chars().peekable()));
+        let msg = |iter_: & CountIter<state::ByClone<std::iter::Peekable<std::str::Chars>>>| iter_.index;
+        let res: Result<[char; 2], Vec<usize>> = alternative!(
+            errors::try_parse(builders::expect(['a', 'b'], msg)),
+            builders::expect(['a', 'c'], msg)
+        )(&mut iter);
+
+        assert_eq!(Ok(['a', 'c']), res); // expect success
+
+        assert_eq!(Some('-'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn parse_choice_cut_on_ac() {
+        let mut iter = count!("ac-".chars());
+        let parsers: [boxed::BoxedParser<CountIter<std::str::Chars>, errors::Cut<usize>, [char; 1]>; 2] = [
+            errors::try_parse(last!(builders::expect(['a'], cut_msg!()), errors::cut(builders::expect(['b'], cut_msg!())))).boxed(),
+            last!(builders::expect(['a'], cut_msg!()), builders::expect(['c'], cut_msg!())).boxed()
+        ];
+        let res: Result<[char; 1], Vec<errors::Cut<usize>>> = combinators::choice_cut(parsers)(&mut iter);
+
+        assert_eq!(Err(vec![errors::Cut { err: 1, committed: true }]), res); // expect committed failure
+
+        assert_eq!(Some('a'), iter.next());
+        assert_eq!(Some('c'), iter.next());
+    }
+
+    #[test]
+    fn parse_chainl1_cut_arrow_on_8_dash_dash() {
+        let mut iter = count!("8--".chars());
+        let digit = || builders::predicate(|[c]: &[char; 1]| c.is_ascii_digit(), cut_msg!())
+            .map(|[c]| c.to_digit(10).unwrap() as i32);
+        let res: Result<i32, Vec<errors::Cut<usize>>> = combinators::chainl1_cut(
+            digit(),
+            errors::try_parse(last!(builders::expect(['-'], cut_msg!()), errors::cut(builders::expect(['>'], cut_msg!()))))
+                .map(|_| |x: i32, y: i32| x - y)
+        )(&mut iter);
+
+        assert_eq!(Err(vec![errors::Cut { err: 2, committed: true }]), res); // expect committed failure
+
+        assert_eq!(Some('-'), iter.next());
+        assert_eq!(Some('-'), iter.next());
+        assert_eq!(None, iter.next());
+    }
+}
@@ -0,0 +1,165 @@
+use core::cell::RefCell;
+use alloc::{ vec::Vec, collections::BTreeMap, rc::{ Rc, Weak } };
+
+use super::{ parser, state::{ Checkpoint, Snapshot, Position } };
+
+/// Counts tokens like `CountIter`, tagging the parse with a session so each input gets its own `memo` tables
+#[derive(Debug, Clone)]
+pub struct MemoIter<Iter> {
+    pub iter: Iter,
+    pub index: usize,
+    session: Rc<()>
+}
+
+impl<Iter> MemoIter<Iter> {
+    /// Starts a fresh memoised parse of `iter`
+    pub fn new(iter: Iter) -> Self {
+        MemoIter {
+            iter,
+            index: 0,
+            session: Rc::new(())
+        }
+    }
+}
+
+impl<Iter: Iterator> Iterator for MemoIter<Iter> {
+    type Item = Iter::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.iter.next() {
+            Option::Some(token) => { self.index += 1; Option::Some(token) },
+            Option::None => Option::None
+        }
+    }
+}
+
+impl<Iter: Checkpoint> Checkpoint for MemoIter<Iter> {
+    type Token = (Iter::Token, usize);
+
+    fn save(&self) -> Self::Token {
+        (self.iter.save(), self.index)
+    }
+
+    fn restore(&mut self, (token, index): Self::Token) {
+        self.iter.restore(token);
+        self.index = index;
+    }
+
+    fn release(&mut self, (token, _index): Self::Token) {
+        self.iter.release(token);
+    }
+}
+
+impl<Iter: Snapshot> Snapshot for MemoIter<Iter> {}
+
+impl<Iter> Position for MemoIter<Iter> {
+    type Pos = usize;
+
+    fn position(&self) -> usize {
+        self.index
+    }
+}
+
+pub const fn memo_index<Iter, Err>()
+    -> parser![MemoIter<Iter>, Err, usize]
+{
+    |iter| Result::Ok(iter.index)
+}
+
+/// For each live session, the result at each position with the inner iterator and index after it
+pub(crate) type MemoTable<Iter, Err, T> = RefCell<Vec<(Weak<()>, BTreeMap<usize, (Result<T, Err>, Iter, usize)>)>>;
+
+/// Finds the table for the iterator's session, dropping those of inputs that are no longer being parsed
+fn session_table<'t, Iter, Err, T>(
+    tables: &'t mut Vec<(Weak<()>, BTreeMap<usize, (Result<T, Err>, Iter, usize)>)>,
+    iter: & MemoIter<Iter>
+)
+    -> &'t mut BTreeMap<usize, (Result<T, Err>, Iter, usize)>
+{
+    tables.retain(|(session, _table)| session.strong_count() > 0);
+    let i: usize = match tables.iter().position(|(session, _table)| core::ptr::eq(session.as_ptr(), Rc::as_ptr(& iter.session))) {
+        Option::Some(i) => i,
+        Option::None => {
+            tables.push((Rc::downgrade(& iter.session), BTreeMap::new()));
+            tables.len() - 1
+        }
+    };
+    &mut tables[i].1
+}
+
+pub(crate) fn memo_lookup<Iter: Clone, Err: Clone, T: Clone>(
+    table: & MemoTable<Iter, Err, T>,
+    iter: &mut MemoIter<Iter>
+)
+    -> Option<Result<T, Err>>
+{
+    let mut tables = table.borrow_mut();
+    let (res, post, index) = session_table(&mut tables, iter).get(& iter.index)?;
+    iter.iter = post.clone();
+    iter.index = *index;
+    Option::Some(res.clone())
+}
+
+pub(crate) fn memo_insert<Iter: Clone, Err, T>(
+    table: & MemoTable<Iter, Err, T>,
+    pre: usize,
+    res: Result<T, Err>,
+    iter: & MemoIter<Iter>
+)
+{
+    session_table(&mut table.borrow_mut(), iter).insert(pre, (res, iter.iter.clone(), iter.index));
+}
+
+/// (packrat) Runs the parser at most once per position, replaying the result and end state afterwards
+pub fn memo<Iter: Clone, Err: Clone, T: Clone>(
+    parser: parser![MemoIter<Iter>, Err, T]
+)
+    -> parser![MemoIter<Iter>, Err, T]
+{
+    let table: MemoTable<Iter, Err, T> = RefCell::new(Vec::new());
+    move |iter| {
+        if let Option::Some(res) = memo_lookup(& table, iter) {
+            return res;
+        }
+        let pre: usize = iter.index;
+        let res = parser(iter);
+        memo_insert(& table, pre, res.clone(), iter);
+        res
+    }
+}
+
+/// (packrat) Like `memo` but grows a seed at each position, so left-recursive rules terminate and associate to the left
+/// (the seed fails with `msg` until the rule's non-recursive branch succeeds)
+pub fn memo_left<Iter: Clone, Err: Clone, T: Clone>(
+    msg: impl Fn(& MemoIter<Iter>) -> Err,
+    parser: parser![MemoIter<Iter>, Err, T]
+)
+    -> parser![MemoIter<Iter>, Err, T]
+{
+    let table: MemoTable<Iter, Err, T> = RefCell::new(Vec::new());
+    move |iter| {
+        if let Option::Some(res) = memo_lookup(& table, iter) {
+            return res;
+        }
+        let pre: MemoIter<Iter> = iter.clone();
+        memo_insert(& table, pre.index, Result::Err(msg(iter)), iter);
+        loop {
+            *iter = pre.clone();
+            let res = parser(iter);
+            let mut tables = table.borrow_mut();
+            let seed = session_table(&mut tables, iter).get_mut(& pre.index).expect("memo entry removed whilst growing");
+            match (& res, & seed.0) {
+                (Result::Ok(_), Result::Ok(_)) if iter.index <= seed.2 => break,
+                (Result::Err(_), Result::Ok(_)) => break,
+                _ => {}
+            };
+            let failed = res.is_err();
+            *seed = (res, iter.iter.clone(), iter.index);
+            if failed {
+                break;
+            }
+        }
+        *iter = pre;
+        memo_lookup(& table, iter).expect("memo entry removed whilst growing")
+    }
+}
@@ -1,4 +1,6 @@
-use super::parser;
+use alloc::vec::Vec;
+
+use super::{ parser, errors::Commit };
 
 /// (pure) Turns a producer into a trivial parser
 pub const fn pure<Iter, Err, T>(
@@ -279,6 +281,56 @@ pub const fn bind4<Iter, Err, T0, T1, T2, T3, U, UParser: FnOnce(&mut Iter) -> R
     })()
 }
 
+/// Tuples of parsers that can be run one after another, collecting their results into a tuple
+pub trait Sequence<Iter, Err> {
+    type Output;
+
+    fn parse_seq(&self, iter: &mut Iter) -> Result<Self::Output, Err>;
+}
+
+macro_rules! sequence_tuple {
+    ($(($($p:ident $t:ident),+)),*) => ($(
+        impl<Iter, Err, $($t, $p: Fn(&mut Iter) -> Result<$t, Err>),+> Sequence<Iter, Err> for ($($p,)+) {
+            type Output = ($($t,)+);
+
+            #[allow(non_snake_case)]
+            fn parse_seq(&self, iter: &mut Iter) -> Result<Self::Output, Err> {
+                let ($($p,)+) = self;
+                Result::Ok(($(
+                    match $p(iter) {
+                        Result::Ok(t) => t,
+                        Result::Err(err) => return Result::Err(err)
+                    },
+                )+))
+            }
+        }
+    )*)
+}
+
+sequence_tuple!(
+    (P0 T0),
+    (P0 T0, P1 T1),
+    (P0 T0, P1 T1, P2 T2),
+    (P0 T0, P1 T1, P2 T2, P3 T3),
+    (P0 T0, P1 T1, P2 T2, P3 T3, P4 T4),
+    (P0 T0, P1 T1, P2 T2, P3 T3, P4 T4, P5 T5),
+    (P0 T0, P1 T1, P2 T2, P3 T3, P4 T4, P5 T5, P6 T6),
+    (P0 T0, P1 T1, P2 T2, P3 T3, P4 T4, P5 T5, P6 T6, P7 T7),
+    (P0 T0, P1 T1, P2 T2, P3 T3, P4 T4, P5 T5, P6 T6, P7 T7, P8 T8),
+    (P0 T0, P1 T1, P2 T2, P3 T3, P4 T4, P5 T5, P6 T6, P7 T7, P8 T8, P9 T9),
+    (P0 T0, P1 T1, P2 T2, P3 T3, P4 T4, P5 T5, P6 T6, P7 T7, P8 T8, P9 T9, P10 T10),
+    (P0 T0, P1 T1, P2 T2, P3 T3, P4 T4, P5 T5, P6 T6, P7 T7, P8 T8, P9 T9, P10 T10, P11 T11)
+);
+
+/// Runs a tuple of parsers in order, returning a tuple of their results (up to 12, use `fmap` on the result instead of `fmapN`)
+pub const fn seq<Iter, Err, S: Sequence<Iter, Err>>(
+    parsers: S
+)
+    -> parser![Iter, Err, S::Output]
+{
+    move |iter| parsers.parse_seq(iter)
+}
+
 /// (<|>) Runs the first parser and then, on error, runs the 2nd parser
 /// (DOES 👏 NOT 👏 REWIND 👏, use `error::try_parse` for that)
 pub const fn otherwise<Iter, Err, T>(
@@ -295,3 +347,20 @@ pub const fn otherwise<Iter, Err, T>(
         }
     }
 }
+
+/// As `otherwise`, but errors that passed an `errors::cut` are returned without trying the 2nd parser
+pub const fn otherwise_cut<Iter, Err: Commit, T>(
+    parser0: parser![Iter, Vec<Err>, T],
+    parser1: parser![Iter, Vec<Err>, T]
+)
+    -> parser![Iter, Vec<Err>, T]
+{
+    move |iter| match parser0(iter) {
+        Result::Ok(t) => Result::Ok(t),
+        Result::Err(err0) if err0.is_committed() => Result::Err(err0),
+        Result::Err(mut err0) => match parser1(iter) {
+            Result::Ok(t) => Result::Ok(t),
+            Result::Err(mut err1) => Result::Err({ err0.append(&mut err1); err0 })
+        }
+    }
+}
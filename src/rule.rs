@@ -0,0 +1,85 @@
+use core::cell::OnceCell;
+use alloc::{ boxed::Box, rc::{ Rc, Weak } };
+
+use super::{ traits::Parser, boxed::BoxedParser };
+
+enum RuleRef<'a, Iter, Err, T> {
+    Strong(Rc<OnceCell<BoxedParser<'a, Iter, Err, T>>>),
+    Weak(Weak<OnceCell<BoxedParser<'a, Iter, Err, T>>>)
+}
+
+/// A forward-declared parser that can be used before it is defined
+/// (clones share the definition, so mutually recursive rules form an `Rc` cycle; use `downgrade` to break it)
+pub struct Rule<'a, Iter, Err, T>(RuleRef<'a, Iter, Err, T>);
+
+impl<'a, Iter, Err, T> Rule<'a, Iter, Err, T> {
+    /// Declares a rule with no definition yet
+    pub fn new() -> Self {
+        Rule(RuleRef::Strong(Rc::new(OnceCell::new())))
+    }
+
+    /// Gives the rule its definition (panics if it already has one)
+    pub fn define(
+        &self,
+        parser: impl Fn(&mut Iter) -> Result<T, Err> + 'a
+    )
+    {
+        let cell = match &self.0 {
+            RuleRef::Strong(cell) => cell.clone(),
+            RuleRef::Weak(weak) => weak.upgrade().expect("rule defined after it was dropped")
+        };
+        if cell.set(Box::new(parser)).is_err() {
+            panic!("rule defined twice")
+        }
+    }
+
+    /// A handle to the same rule that does not keep its definition alive
+    pub fn downgrade(&self) -> Self {
+        match &self.0 {
+            RuleRef::Strong(cell) => Rule(RuleRef::Weak(Rc::downgrade(cell))),
+            RuleRef::Weak(weak) => Rule(RuleRef::Weak(weak.clone()))
+        }
+    }
+}
+
+impl<'a, Iter, Err, T> Default for Rule<'a, Iter, Err, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, Iter, Err, T> Clone for Rule<'a, Iter, Err, T> {
+    fn clone(&self) -> Self {
+        match &self.0 {
+            RuleRef::Strong(cell) => Rule(RuleRef::Strong(cell.clone())),
+            RuleRef::Weak(weak) => Rule(RuleRef::Weak(weak.clone()))
+        }
+    }
+}
+
+impl<'a, Iter, Err, T> Parser<Iter> for Rule<'a, Iter, Err, T> {
+    type Output = T;
+    type Error = Err;
+
+    fn parse(&self, iter: &mut Iter) -> Result<T, Err> {
+        match &self.0 {
+            RuleRef::Strong(cell) => cell.get().expect("rule used before it was defined")(iter),
+            RuleRef::Weak(weak) => weak
+                .upgrade()
+                .expect("rule used after it was dropped")
+                .get()
+                .expect("rule used before it was defined")(iter)
+        }
+    }
+}
+
+/// (fix) Builds a parser from a function of itself, for directly recursive grammars
+pub fn recursive<'a, Iter: 'a, Err: 'a, T: 'a, P: Fn(&mut Iter) -> Result<T, Err> + 'a>(
+    f: impl FnOnce(Rule<'a, Iter, Err, T>) -> P
+)
+    -> impl Fn(&mut Iter) -> Result<T, Err> + 'a
+{
+    let rule: Rule<'a, Iter, Err, T> = Rule::new();
+    rule.define(f(rule.downgrade()));
+    rule.into_fn()
+}
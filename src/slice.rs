@@ -0,0 +1,158 @@
+use super::{ parser, state::{ Checkpoint, Snapshot, Position } };
+
+/// Inputs backed by a borrowed buffer, whose consumed regions can be handed out without copying
+pub trait SliceInput<'a> {
+    type Slice: ?Sized + 'a;
+
+    /// The position in the buffer of the next token
+    fn offset(&self) -> usize;
+
+    /// The region of the buffer between two offsets
+    fn slice(&self, start: usize, end: usize) -> &'a Self::Slice;
+}
+
+/// Reads the `char`s of a `&str`, tracking the byte offset
+#[derive(Debug, Clone)]
+pub struct StrIter<'a> {
+    pub source: &'a str,
+    pub offset: usize
+}
+
+impl<'a> StrIter<'a> {
+    pub const fn new(source: &'a str) -> Self {
+        StrIter { source, offset: 0 }
+    }
+}
+
+impl<'a> Iterator for StrIter<'a> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.source[self.offset..].chars().next() {
+            Option::Some(c) => { self.offset += c.len_utf8(); Option::Some(c) },
+            Option::None => Option::None
+        }
+    }
+}
+
+impl<'a> Checkpoint for StrIter<'a> {
+    type Token = usize;
+
+    fn save(&self) -> usize {
+        self.offset
+    }
+
+    fn restore(&mut self, offset: usize) {
+        self.offset = offset;
+    }
+}
+
+impl<'a> Snapshot for StrIter<'a> {}
+
+impl<'a> Position for StrIter<'a> {
+    type Pos = usize;
+
+    fn position(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<'a> SliceInput<'a> for StrIter<'a> {
+    type Slice = str;
+
+    fn offset(&self) -> usize {
+        self.offset
+    }
+
+    fn slice(&self, start: usize, end: usize) -> &'a str {
+        &self.source[start..end]
+    }
+}
+
+/// Reads the elements of a `&[T]`, tracking the index
+#[derive(Debug, Clone)]
+pub struct SliceIter<'a, T> {
+    pub source: &'a [T],
+    pub offset: usize
+}
+
+impl<'a, T> SliceIter<'a, T> {
+    pub const fn new(source: &'a [T]) -> Self {
+        SliceIter { source, offset: 0 }
+    }
+}
+
+impl<'a, T: Clone> Iterator for SliceIter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.source.get(self.offset) {
+            Option::Some(t) => { self.offset += 1; Option::Some(t.clone()) },
+            Option::None => Option::None
+        }
+    }
+}
+
+impl<'a, T> Checkpoint for SliceIter<'a, T> {
+    type Token = usize;
+
+    fn save(&self) -> usize {
+        self.offset
+    }
+
+    fn restore(&mut self, offset: usize) {
+        self.offset = offset;
+    }
+}
+
+impl<'a, T> Snapshot for SliceIter<'a, T> {}
+
+impl<'a, T> Position for SliceIter<'a, T> {
+    type Pos = usize;
+
+    fn position(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<'a, T> SliceInput<'a> for SliceIter<'a, T> {
+    type Slice = [T];
+
+    fn offset(&self) -> usize {
+        self.offset
+    }
+
+    fn slice(&self, start: usize, end: usize) -> &'a [T] {
+        &self.source[start..end]
+    }
+}
+
+pub const fn get_offset<'a, Iter: SliceInput<'a>, Err>()
+    -> parser![Iter, Err, usize]
+{
+    |iter| Result::Ok(iter.offset())
+}
+
+/// Runs the parser and returns the region of input it consumed instead of its result
+pub const fn recognize<'a, Iter: SliceInput<'a>, Err, T>(
+    parser: parser![Iter, Err, T]
+)
+    -> parser![Iter, Err, &'a Iter::Slice]
+{
+    move |iter| {
+        let start: usize = iter.offset();
+        parser(iter).map(|_t| iter.slice(start, iter.offset()))
+    }
+}
+
+/// Runs the parser and returns the region of input it consumed alongside its result
+pub const fn consumed<'a, Iter: SliceInput<'a>, Err, T>(
+    parser: parser![Iter, Err, T]
+)
+    -> parser![Iter, Err, (&'a Iter::Slice, T)]
+{
+    move |iter| {
+        let start: usize = iter.offset();
+        parser(iter).map(|t| (iter.slice(start, iter.offset()), t))
+    }
+}
@@ -1,5 +1,115 @@
+use core::cell::Cell;
+use alloc::{ vec, vec::Vec };
+
 use super::parser;
 
+/// States that can be saved and later rewound to, without cloning the whole state
+pub trait Checkpoint {
+    type Token;
+
+    /// Records the current position
+    fn save(&self) -> Self::Token;
+
+    /// Moves to a previously saved position
+    fn restore(&mut self, token: Self::Token);
+
+    /// Gives back a token that will not be restored, so the state can forget what it kept for it
+    fn release(&mut self, _token: Self::Token) {}
+}
+
+/// States that know how far through the input they are, so parsers can tell whether any was consumed
+pub trait Position {
+    type Pos: PartialEq;
+
+    fn position(&self) -> Self::Pos;
+}
+
+/// Checkpoints that only record a position (never a stack or user state), cheap enough to take before every token
+/// and able to return forwards again after rewinding (their tokens need not be released)
+pub trait Snapshot: Checkpoint {}
+
+/// Shows `f` the state as it was at `token` and then returns to the current state
+pub fn look_back<Iter: Snapshot, R>(
+    iter: &mut Iter,
+    token: Iter::Token,
+    f: impl FnOnce(&Iter) -> R
+)
+    -> R
+{
+    let post: Iter::Token = iter.save();
+    iter.restore(token);
+    let r: R = f(iter);
+    iter.restore(post);
+    r
+}
+
+macro_rules! checkpoint_by_clone {
+    ($([$($g:tt)*] $t:ty),*) => ($(
+        impl<$($g)*> Checkpoint for $t {
+            type Token = Self;
+
+            fn save(&self) -> Self { self.clone() }
+
+            fn restore(&mut self, token: Self) { *self = token; }
+        }
+    )*)
+}
+
+checkpoint_by_clone!(
+    ['a] core::str::Chars<'a>,
+    ['a] core::str::CharIndices<'a>,
+    ['a] core::str::Bytes<'a>,
+    ['a, T] core::slice::Iter<'a, T>,
+    [T: Clone] alloc::vec::IntoIter<T>
+);
+
+macro_rules! snapshot {
+    ($([$($g:tt)*] $t:ty),*) => ($(
+        impl<$($g)*> Snapshot for $t {}
+    )*)
+}
+
+snapshot!(
+    ['a] core::str::Chars<'a>,
+    ['a] core::str::CharIndices<'a>,
+    ['a] core::str::Bytes<'a>,
+    ['a, T] core::slice::Iter<'a, T>
+);
+
+/// Checkpoints any cloneable iterator (e.g. `Peekable`, `Map` or a user's own) by cloning it
+#[derive(Debug, Clone)]
+pub struct ByClone<Iter> {
+    pub iter: Iter
+}
+
+impl<Iter> ByClone<Iter> {
+    pub const fn new(iter: Iter) -> Self {
+        ByClone { iter }
+    }
+}
+
+impl<Iter: Iterator> Iterator for ByClone<Iter> {
+    type Item = Iter::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
+
+impl<Iter: Clone> Checkpoint for ByClone<Iter> {
+    type Token = Iter;
+
+    fn save(&self) -> Iter {
+        self.iter.clone()
+    }
+
+    fn restore(&mut self, token: Iter) {
+        self.iter = token;
+    }
+}
+
+impl<Iter: Clone> Snapshot for ByClone<Iter> {}
+
 pub const fn lense<Iter, Jter, Err, T>(
     sect: impl Fn(&mut Jter) -> &mut Iter,
     parser: parser![Iter, Err, T]
@@ -37,16 +147,130 @@ impl<Iter: Iterator> Iterator for CountIter<Iter> {
 
 }
 
+impl<Iter: Checkpoint> Checkpoint for CountIter<Iter> {
+    type Token = (Iter::Token, usize);
+
+    fn save(&self) -> Self::Token {
+        (self.iter.save(), self.index)
+    }
+
+    fn restore(&mut self, (token, index): Self::Token) {
+        self.iter.restore(token);
+        self.index = index;
+    }
+
+    fn release(&mut self, (token, _index): Self::Token) {
+        self.iter.release(token);
+    }
+}
+
+impl<Iter: Snapshot> Snapshot for CountIter<Iter> {}
+
+impl<Iter> Position for CountIter<Iter> {
+    type Pos = usize;
+
+    fn position(&self) -> usize {
+        self.index
+    }
+}
+
 pub const fn count<Iter, Err>()
     -> parser![CountIter<Iter>, Err, usize]
 {
     |iter| Result::Ok(iter.index)
 }
 
+/// A change made to the stack of a `StackIter`, kept whilst a checkpoint might undo it
+#[derive(Debug, Clone)]
+enum StackOp<Symbol> {
+    Push,
+    Pop(Symbol)
+}
+
 #[derive(Debug, Clone)]
 pub struct StackIter<Iter, Symbol> {
     pub iter: Iter,
-    pub stack: Vec<Symbol>
+    stack: Vec<Symbol>,
+    /// Every push and pop since the outermost checkpoint that is still live
+    trail: Vec<StackOp<Symbol>>,
+    /// How many checkpoints are nested around the current position
+    live: Cell<usize>
+}
+
+impl<Iter, Symbol> StackIter<Iter, Symbol> {
+    pub const fn new(iter: Iter) -> Self {
+        StackIter::with_stack(iter, vec![])
+    }
+
+    pub const fn with_stack(iter: Iter, stack: Vec<Symbol>) -> Self {
+        StackIter { iter, stack, trail: vec![], live: Cell::new(0) }
+    }
+
+    /// The symbols on the stack, from the bottom
+    pub fn stack(&self) -> &[Symbol] {
+        & self.stack
+    }
+
+    fn record(&mut self, op: StackOp<Symbol>) {
+        if self.live.get() > 0 {
+            self.trail.push(op);
+        }
+    }
+
+    /// Pushes onto the stack, recording it for any live checkpoint
+    pub fn push(&mut self, symbol: Symbol) {
+        self.record(StackOp::Push);
+        self.stack.push(symbol);
+    }
+
+    /// Pops from the stack, recording it for any live checkpoint
+    pub fn pop(&mut self) -> Option<Symbol> where Symbol: Clone {
+        let symbol: Symbol = self.stack.pop()?;
+        self.record(StackOp::Pop(symbol.clone()));
+        Option::Some(symbol)
+    }
+
+    fn close(&mut self, depth: usize) {
+        self.live.set(depth - 1);
+        if depth == 1 {
+            self.trail.clear();
+        }
+    }
+}
+
+impl<Iter: Checkpoint, Symbol> Checkpoint for StackIter<Iter, Symbol> {
+    /// The inner token, the length of the trail and how deeply nested the checkpoint is
+    type Token = (Iter::Token, usize, usize);
+
+    fn save(&self) -> Self::Token {
+        self.live.set(self.live.get() + 1);
+        (self.iter.save(), self.trail.len(), self.live.get())
+    }
+
+    fn restore(&mut self, (token, len, depth): Self::Token) {
+        self.iter.restore(token);
+        while self.trail.len() > len {
+            match self.trail.pop() {
+                Option::Some(StackOp::Push) => { self.stack.pop(); },
+                Option::Some(StackOp::Pop(symbol)) => self.stack.push(symbol),
+                Option::None => {}
+            };
+        }
+        self.close(depth);
+    }
+
+    fn release(&mut self, (token, _len, depth): Self::Token) {
+        self.iter.release(token);
+        self.close(depth);
+    }
+}
+
+impl<Iter: Position, Symbol> Position for StackIter<Iter, Symbol> {
+    type Pos = Iter::Pos;
+
+    fn position(&self) -> Self::Pos {
+        self.iter.position()
+    }
 }
 
 impl<Iter: Iterator, Symbol> Iterator for StackIter<Iter, Symbol> {
@@ -66,8 +290,8 @@ pub const fn stack_action<Iter, Symbol: Clone + PartialEq, Err, const N: usize>(
     move |iter| {
         for action in actions.iter() {
             match action {
-                (true, a) => iter.stack.push(a.clone()),
-                (false, a) => match iter.stack.pop() {
+                (true, a) => iter.push(a.clone()),
+                (false, a) => match iter.pop() {
                     Option::Some(b) => if a != &b { return Result::Err(pop_msg(& iter.iter, a.clone(), Option::Some(b))) },
                     Option::None => { return Result::Err(pop_msg(& iter.iter, a.clone(), Option::None)) }
                 }
@@ -83,7 +307,7 @@ pub const fn stack_push<Iter, Symbol: Clone + PartialEq, Err>(
     -> parser![StackIter<Iter, Symbol>, Err, ()]
 {
     move |iter| {
-        iter.stack.push(a.clone());
+        iter.push(a.clone());
         Result::Ok(())
     }
 }
@@ -94,7 +318,7 @@ pub const fn stack_pop<Iter, Symbol: Clone + PartialEq, Err>(
 )
     -> parser![StackIter<Iter, Symbol>, Err, ()]
 {
-    move |iter| match iter.stack.pop() {
+    move |iter| match iter.pop() {
         Option::Some(b) => if a != b {
             Result::Err(pop_msg(& iter.iter, a.clone(), Option::Some(b)))
         } else {
@@ -118,6 +342,98 @@ impl<Iter: Iterator, State> Iterator for CustomIter<Iter, State> {
     }
 }
 
+impl<Iter: Checkpoint, State: Clone> Checkpoint for CustomIter<Iter, State> {
+    type Token = (Iter::Token, State);
+
+    fn save(&self) -> Self::Token {
+        (self.iter.save(), self.state.clone())
+    }
+
+    fn restore(&mut self, (token, state): Self::Token) {
+        self.iter.restore(token);
+        self.state = state;
+    }
+
+    fn release(&mut self, (token, _state): Self::Token) {
+        self.iter.release(token);
+    }
+}
+
+impl<Iter: Position, State> Position for CustomIter<Iter, State> {
+    type Pos = Iter::Pos;
+
+    fn position(&self) -> Self::Pos {
+        self.iter.position()
+    }
+}
+
+/// States that keep the errors recovered from, so one parse can report every error in the input
+pub trait ErrorLog<Err> {
+    fn log_error(&mut self, err: Err);
+}
+
+/// Keeps the errors recovered from alongside the input, checkpointing only how many have been logged
+#[derive(Debug, Clone)]
+pub struct RecoverIter<Iter, Err> {
+    pub iter: Iter,
+    errors: Vec<Err>
+}
+
+impl<Iter, Err> RecoverIter<Iter, Err> {
+    pub const fn new(iter: Iter) -> Self {
+        RecoverIter { iter, errors: vec![] }
+    }
+
+    /// The errors logged so far, in the order they were found
+    pub fn errors(&self) -> &[Err] {
+        & self.errors
+    }
+
+    pub fn into_errors(self) -> Vec<Err> {
+        self.errors
+    }
+}
+
+impl<Iter, Err> ErrorLog<Err> for RecoverIter<Iter, Err> {
+    fn log_error(&mut self, err: Err) {
+        self.errors.push(err);
+    }
+}
+
+impl<Iter: Checkpoint, Err> Checkpoint for RecoverIter<Iter, Err> {
+    /// The inner token and the number of errors logged
+    type Token = (Iter::Token, usize);
+
+    fn save(&self) -> Self::Token {
+        (self.iter.save(), self.errors.len())
+    }
+
+    fn restore(&mut self, (token, len): Self::Token) {
+        self.iter.restore(token);
+        self.errors.truncate(len);
+    }
+
+    fn release(&mut self, (token, _len): Self::Token) {
+        self.iter.release(token);
+    }
+}
+
+impl<Iter: Position, Err> Position for RecoverIter<Iter, Err> {
+    type Pos = Iter::Pos;
+
+    fn position(&self) -> Self::Pos {
+        self.iter.position()
+    }
+}
+
+impl<Iter: Iterator, Err> Iterator for RecoverIter<Iter, Err> {
+    type Item = Iter::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
+
 pub const fn update_state<Iter, State, Err>(
     f: impl Fn(&mut State)
 )
@@ -1,6 +1,10 @@
-use crate::parser;
+use core::fmt::{ self, Display, Formatter };
+use alloc::{ vec::Vec, string::String, format };
+
+use crate::{ parser, errors::ParseError, state::{ Checkpoint, Snapshot, Position } };
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TextInfo{
     pub file: String,
     pub line: usize,
@@ -13,6 +17,20 @@ pub struct TextIter<Iter>{
     pub info: TextInfo
 }
 
+/// Shows "file:line:column", counting columns from 1
+impl Display for TextInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.index + 1)
+    }
+}
+
+impl<Iter> TextIter<Iter> {
+    /// Starts reading `file` at line 1, column 0 (the convention `render_error` expects)
+    pub fn new(file: String, iter: Iter) -> Self {
+        TextIter { iter, info: TextInfo { file, line: 1, index: 0 } }
+    }
+}
+
 impl<Iter: Iterator<Item = char>> Iterator for TextIter<Iter> {
     type Item = char;
 
@@ -25,8 +43,112 @@ impl<Iter: Iterator<Item = char>> Iterator for TextIter<Iter> {
     }
 }
 
+impl<Iter: Checkpoint> Checkpoint for TextIter<Iter> {
+    type Token = (Iter::Token, usize, usize);
+
+    fn save(&self) -> Self::Token {
+        (self.iter.save(), self.info.line, self.info.index)
+    }
+
+    fn restore(&mut self, (token, line, index): Self::Token) {
+        self.iter.restore(token);
+        self.info.line = line;
+        self.info.index = index;
+    }
+
+    fn release(&mut self, (token, _line, _index): Self::Token) {
+        self.iter.release(token);
+    }
+}
+
+impl<Iter: Snapshot> Snapshot for TextIter<Iter> {}
+
+impl<Iter> Position for TextIter<Iter> {
+    type Pos = (usize, usize);
+
+    fn position(&self) -> Self::Pos {
+        (self.info.line, self.info.index)
+    }
+}
+
 pub const fn get_text_info<Iter, Err>()
     -> parser![TextIter<Iter>, Err, TextInfo]
 {
     |iter| Ok(iter.info.clone())
-}
\ No newline at end of file
+}
+
+const RED: &str = "1;31";
+const GREEN: &str = "1;32";
+const BLUE: &str = "1;34";
+
+fn paint(colour: bool, code: &str, text: &str) -> String {
+    if colour { format!("\x1b[{code}m{text}\x1b[0m") } else { String::from(text) }
+}
+
+fn render_snippet(
+    sources: &[(&str, &str)],
+    colour: bool,
+    (kind, kind_code): (&str, &str),
+    message: String,
+    info: & TextInfo,
+    (marker, marker_code): (&str, &str)
+)
+    -> String
+{
+    let number: String = format!("{}", info.line);
+    let gutter: String = " ".repeat(number.len());
+    let bar: String = paint(colour, BLUE, "|");
+    let mut out: String = format!(
+        "{}{}\n{gutter}{} {}:{}:{}\n",
+        paint(colour, kind_code, kind),
+        paint(colour, "1", & format!(": {message}")),
+        paint(colour, BLUE, "-->"),
+        info.file,
+        info.line,
+        info.index + 1
+    );
+    let text: Option<&str> = sources
+        .iter()
+        .find(|(file, _source)| *file == info.file)
+        .and_then(|(_file, source)| source.lines().nth(info.line.checked_sub(1)?));
+    if let Option::Some(text) = text {
+        let pad: String = text.chars().take(info.index).map(|c| if c == '\t' { '\t' } else { ' ' }).collect();
+        out += & format!(
+            "{gutter} {bar}\n{} {bar} {text}\n{gutter} {bar} {pad}{}\n",
+            paint(colour, BLUE, & number),
+            paint(colour, marker_code, marker)
+        );
+    }
+    out
+}
+
+/// Renders the error like a compiler diagnostic, quoting the offending line from `sources` (pairs of file name and text)
+/// with `Contextual` labels as notes, and ANSI colour if `colour` is set
+pub fn render_error(
+    sources: &[(&str, &str)],
+    colour: bool,
+    parse_err: ParseError<TextInfo>
+)
+    -> String
+{
+    match parse_err {
+        ParseError::Silent =>
+            String::new(),
+        ParseError::Message(name, info) =>
+            render_snippet(sources, colour, ("error", RED), format!("expected {name}"), & info, ("^", RED)),
+        ParseError::Contextual(name, info, err) =>
+            format!(
+                "{}{}",
+                render_error(sources, colour, *err),
+                render_snippet(sources, colour, ("note", GREEN), format!("whilst parsing {name}"), & info, ("-", BLUE))
+            ),
+        ParseError::ErrBundle(errs) | ParseError::ErrChoice(errs) =>
+            errs.into_iter()
+                .map(|err| render_error(sources, colour, err))
+                .filter(|out| !out.is_empty())
+                .collect::<Vec<String>>()
+                .join("\n"),
+        ParseError::Committed(err) =>
+            render_error(sources, colour, *err)
+    }
+}
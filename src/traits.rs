@@ -0,0 +1,126 @@
+use alloc::{ vec::Vec, string::String };
+
+use super::{ parser, monadic, errors::{ self, ParseError, Commit }, boxed::{ self, BoxedParser, RcParser }, state::Checkpoint };
+
+/// A nameable parser over `Iter`; every `parser![Iter, Err, T]` closure is one of these
+pub trait Parser<Iter> {
+    type Output;
+    type Error;
+
+    /// Runs the parser on the state
+    fn parse(&self, iter: &mut Iter) -> Result<Self::Output, Self::Error>;
+
+    /// Turns the parser back into a closure for use with the free combinators
+    fn into_fn(self)
+        -> parser![Iter, Self::Error, Self::Output]
+    where
+        Self: Sized
+    {
+        move |iter| self.parse(iter)
+    }
+
+    /// Borrows the parser as a closure without consuming it
+    fn by_ref(&self)
+        -> parser![Iter, Self::Error, Self::Output]
+    {
+        move |iter| self.parse(iter)
+    }
+
+    /// Method form of `boxed::boxed`
+    fn boxed<'a>(self)
+        -> BoxedParser<'a, Iter, Self::Error, Self::Output>
+    where
+        Self: Sized + 'a
+    {
+        boxed::boxed(move |iter: &mut Iter| self.parse(iter))
+    }
+
+    /// Method form of `boxed::shared`
+    fn shared<'a>(self)
+        -> RcParser<'a, Iter, Self::Error, Self::Output>
+    where
+        Self: Sized + 'a
+    {
+        boxed::shared(move |iter: &mut Iter| self.parse(iter))
+    }
+
+    /// (<$>) Method form of `monadic::fmap`
+    fn map<U>(
+        self,
+        f: impl Fn(Self::Output) -> U
+    )
+        -> parser![Iter, Self::Error, U]
+    where
+        Self: Sized
+    {
+        monadic::fmap(f, self.into_fn())
+    }
+
+    /// (>>=) Method form of `monadic::bind`
+    fn bind<U, UParser: FnOnce(&mut Iter) -> Result<U, Self::Error>>(
+        self,
+        f: impl Fn(Self::Output) -> UParser
+    )
+        -> parser![Iter, Self::Error, U]
+    where
+        Self: Sized
+    {
+        monadic::bind(self.into_fn(), f)
+    }
+
+    /// (<|>) Method form of `monadic::otherwise`
+    fn or<Err>(
+        self,
+        other: impl Parser<Iter, Output = Self::Output, Error = Vec<Err>>
+    )
+        -> parser![Iter, Vec<Err>, Self::Output]
+    where
+        Self: Sized + Parser<Iter, Error = Vec<Err>>
+    {
+        monadic::otherwise(self.into_fn(), other.into_fn())
+    }
+
+    /// Method form of `monadic::otherwise_cut`
+    fn or_cut<Err: Commit>(
+        self,
+        other: impl Parser<Iter, Output = Self::Output, Error = Vec<Err>>
+    )
+        -> parser![Iter, Vec<Err>, Self::Output]
+    where
+        Self: Sized + Parser<Iter, Error = Vec<Err>>
+    {
+        monadic::otherwise_cut(self.into_fn(), other.into_fn())
+    }
+
+    /// Method form of `errors::try_parse`
+    fn try_parse(self)
+        -> parser![Iter, Self::Error, Self::Output]
+    where
+        Self: Sized,
+        Iter: Checkpoint
+    {
+        errors::try_parse(self.into_fn())
+    }
+
+    /// Method form of `errors::label`
+    fn label<Info>(
+        self,
+        name: String,
+        info_getter: impl Fn(&Iter) -> Info
+    )
+        -> parser![Iter, ParseError<Info>, Self::Output]
+    where
+        Self: Sized + Parser<Iter, Error = ParseError<Info>>
+    {
+        errors::label(name, info_getter, self.into_fn())
+    }
+}
+
+impl<Iter, Err, T, F: Fn(&mut Iter) -> Result<T, Err>> Parser<Iter> for F {
+    type Output = T;
+    type Error = Err;
+
+    fn parse(&self, iter: &mut Iter) -> Result<T, Err> {
+        self(iter)
+    }
+}
@@ -1,17 +1,49 @@
+use alloc::{ vec, vec::Vec };
+
 pub fn result_append<Err, T>(
     resvec: &mut Result<Vec<T>, Vec<Err>>,
     resval: Result<T, Err>
 )
-    -> ()
 {
     match resvec {
         Result::Ok(ts) => match resval {
             Result::Ok(t) => ts.push(t),
             Result::Err(err) => *resvec = Result::Err(vec![err])
         },
-        Result::Err(errs) => match resval {
-            Result::Err(err) => errs.push(err),
-            _ => {}
+        Result::Err(errs) => if let Result::Err(err) = resval {
+            errs.push(err)
         }
     };
-}
\ No newline at end of file
+}
+
+/// Reads the next `N` items, or `None` if the iterator runs out first (consuming what it read either way)
+#[cfg(not(feature = "nightly"))]
+pub fn next_chunk<Iter: Iterator, const N: usize>(
+    iter: &mut Iter
+)
+    -> Option<[Iter::Item; N]>
+{
+    let mut done: bool = false;
+    let chunk: [Option<Iter::Item>; N] = core::array::from_fn(|_| if done {
+        Option::None
+    } else {
+        let item = iter.next();
+        done = item.is_none();
+        item
+    });
+    if done {
+        Option::None
+    } else {
+        Option::Some(chunk.map(Option::unwrap))
+    }
+}
+
+/// Reads the next `N` items, or `None` if the iterator runs out first (consuming what it read either way)
+#[cfg(feature = "nightly")]
+pub fn next_chunk<Iter: Iterator, const N: usize>(
+    iter: &mut Iter
+)
+    -> Option<[Iter::Item; N]>
+{
+    iter.next_chunk::<N>().ok()
+}